@@ -0,0 +1,843 @@
+//! Generation of a *mock pallet* module for the traits of some source files.
+
+use std::collections::{HashMap, HashSet};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+	parse_quote,
+	visit_mut::{self, VisitMut},
+	FnArg, GenericArgument, GenericParam, Ident, Item, ItemTrait, ItemUse, Lifetime, Pat,
+	PathArguments, ReturnType, Signature, TraitItem, TraitItemFn, TraitItemType, Type,
+	TypeParamBound, TypePath, UseTree, Visibility,
+};
+
+/// Options of the generated pallet.
+pub struct Options {
+	/// Path of the module where the traits are defined, imported by the
+	/// generated module.
+	pub path: syn::Path,
+
+	/// Generate a pallet with instances.
+	pub instance: bool,
+}
+
+/// Parsed source file.
+pub struct Source {
+	/// Path of the file, mentioned in the generated documentation.
+	pub name: String,
+
+	/// Content of the file.
+	pub file: syn::File,
+}
+
+/// Trait to mock, with the items the pallet must implement.
+struct MockTrait<'a> {
+	item: &'a ItemTrait,
+	params: Vec<Ident>,
+	types: Vec<&'a TraitItemType>,
+	methods: Vec<&'a TraitItemFn>,
+}
+
+impl<'a> MockTrait<'a> {
+	fn new(item: &'a ItemTrait) -> Result<Self, String> {
+		let name = &item.ident;
+
+		let params = item
+			.generics
+			.params
+			.iter()
+			.map(|param| match param {
+				GenericParam::Type(param) => Ok(param.ident.clone()),
+				_ => Err(format!(
+					"`{name}` has lifetime or const parameters, only type parameters are supported"
+				)),
+			})
+			.collect::<Result<_, _>>()?;
+
+		let mut types = Vec::new();
+		let mut methods = Vec::new();
+		for trait_item in &item.items {
+			match trait_item {
+				TraitItem::Type(ty) if !ty.generics.params.is_empty() => {
+					return Err(format!(
+						"`{name}::{}` has generic parameters, which are not supported",
+						ty.ident
+					))
+				}
+				TraitItem::Type(ty) => types.push(ty),
+				TraitItem::Const(constant) => {
+					return Err(format!(
+						"`{name}::{}` is an associated constant, which is not supported",
+						constant.ident
+					))
+				}
+				// Methods with a default implementation are not mocked.
+				TraitItem::Fn(method) if method.default.is_some() => (),
+				TraitItem::Fn(method) if method.sig.receiver().is_some() => {
+					return Err(format!(
+						"`{name}::{}` has a `self` receiver, which a mock pallet can not implement. Use `mock_builder::mock_object!()` instead",
+						method.sig.ident
+					))
+				}
+				TraitItem::Fn(method) => methods.push(method),
+				_ => (),
+			}
+		}
+
+		Ok(Self {
+			item,
+			params,
+			types,
+			methods,
+		})
+	}
+}
+
+/// Generate the source of a *mock pallet* implementing the traits with the
+/// given names, or all the traits found if none is given.
+pub fn generate(sources: &[Source], names: &[String], options: &Options) -> Result<String, String> {
+	let found = sources
+		.iter()
+		.flat_map(|source| &source.file.items)
+		.filter_map(|item| match item {
+			Item::Trait(item) => Some(item),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+
+	let items = match names.is_empty() {
+		true => found,
+		false => names
+			.iter()
+			.map(|name| {
+				found
+					.iter()
+					.find(|item| item.ident == name)
+					.copied()
+					.ok_or_else(|| format!("trait `{name}` not found"))
+			})
+			.collect::<Result<_, _>>()?,
+	};
+
+	if items.is_empty() {
+		return Err("no traits found".into());
+	}
+
+	let traits = items
+		.into_iter()
+		.map(MockTrait::new)
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let module = pallet(sources, &traits, options);
+	let file = syn::parse2::<syn::File>(module)
+		.map_err(|err| format!("generated module can not be parsed: {err}"))?;
+
+	let traits_doc = traits
+		.iter()
+		.map(|mock_trait| format!("`{}`", mock_trait.item.ident))
+		.collect::<Vec<_>>()
+		.join(", ");
+
+	let sources_doc = sources
+		.iter()
+		.map(|source| format!("`{}`", source.name))
+		.collect::<Vec<_>>()
+		.join(", ");
+
+	Ok(format!(
+		"// @generated by `mock-builder`, do not edit.\n\n\
+		//! Mock pallet for {traits_doc}, generated from {sources_doc}.\n\n{}",
+		layout(&prettyplease::unparse(&file))
+	))
+}
+
+fn pallet(sources: &[Source], traits: &[MockTrait], options: &Options) -> TokenStream {
+	let path = &options.path;
+
+	let (config, pallet, storage, impl_generics, pallet_ty) = match options.instance {
+		true => (
+			quote!(Config<I: 'static = ()>),
+			quote!(Pallet<T, I = ()>),
+			quote!(CallIds<T: Config<I>, I: 'static = ()>),
+			quote!(T: Config<I>, I: 'static),
+			quote!(Pallet<T, I>),
+		),
+		false => (
+			quote!(Config),
+			quote!(Pallet<T>),
+			quote!(CallIds<T: Config>),
+			quote!(T: Config),
+			quote!(Pallet<T>),
+		),
+	};
+
+	let (register, execute) = match options.instance {
+		true => (
+			format_ident!("register_call_instance"),
+			format_ident!("execute_call_instance"),
+		),
+		false => (
+			format_ident!("register_call"),
+			format_ident!("execute_call"),
+		),
+	};
+
+	let uses = uses(sources, path);
+
+	// Methods with the same name in several traits have the trait as part of
+	// their `mock_*` function name.
+	let mut method_counts = HashMap::<String, usize>::new();
+	for mock_trait in traits {
+		for method in &mock_trait.methods {
+			*method_counts
+				.entry(method.sig.ident.to_string())
+				.or_default() += 1;
+		}
+	}
+
+	let mut config_types = Vec::new();
+	let mut config_names = HashSet::new();
+	let mut mock_fns = Vec::new();
+	let mut impls = Vec::new();
+	let mut uses_iter = false;
+
+	for mock_trait in traits {
+		let trait_name = &mock_trait.item.ident;
+		let params = mock_trait.params.iter().cloned().collect::<HashSet<_>>();
+
+		for param in mock_trait.item.generics.type_params() {
+			if config_names.insert(param.ident.clone()) {
+				let mut param = param.clone();
+				param.default = None;
+				param.eq_token = None;
+				Substitute::config(&params).visit_type_param_mut(&mut param);
+				config_types.push(quote!(type #param;));
+			}
+		}
+
+		for ty in &mock_trait.types {
+			if config_names.insert(ty.ident.clone()) {
+				let ident = &ty.ident;
+				let mut bounds = ty.bounds.clone();
+				for bound in &mut bounds {
+					Substitute::config(&params).visit_type_param_bound_mut(bound);
+				}
+				let colon = ty.colon_token;
+				config_types.push(quote!(type #ident #colon #bounds;));
+			}
+		}
+
+		let mut trait_fns = Vec::new();
+		for method in &mock_trait.methods {
+			let prefixed = method_counts[&method.sig.ident.to_string()] > 1;
+			let method = MockMethod::new(&method.sig, &params);
+			uses_iter |= method.iter_item.is_some();
+
+			mock_fns.push(method.register_fn(trait_name, prefixed, &register));
+			trait_fns.push(method.impl_fn(&execute));
+		}
+
+		let trait_args = match mock_trait.params.is_empty() {
+			true => quote!(),
+			false => {
+				let params = &mock_trait.params;
+				quote!(<#(T::#params),*>)
+			}
+		};
+
+		let assoc_types = mock_trait.types.iter().map(|ty| {
+			let ident = &ty.ident;
+			quote!(type #ident = T::#ident;)
+		});
+
+		impls.push(quote! {
+			impl<#impl_generics> #trait_name #trait_args for #pallet_ty {
+				#(#assoc_types)*
+				#(#trait_fns)*
+			}
+		});
+	}
+
+	let mock_iter = uses_iter.then(|| quote!(, MockIter));
+
+	quote! {
+		#[frame_support::pallet(dev_mode)]
+		pub mod pallet {
+			use frame_support::pallet_prelude::*;
+			use mock_builder::{#execute, #register #mock_iter};
+			#(#uses)*
+			use #path::*;
+
+			#[pallet::config]
+			pub trait #config: frame_system::Config {
+				#(#config_types)*
+			}
+
+			#[pallet::pallet]
+			pub struct #pallet(_);
+
+			#[pallet::storage]
+			type #storage = StorageMap<_, _, String, mock_builder::CallId>;
+
+			impl<#impl_generics> #pallet_ty {
+				#(#mock_fns)*
+			}
+
+			#(#impls)*
+		}
+	}
+}
+
+/// Private `use` items of the sources, so the types of the trait signatures
+/// are found. Paths from `crate` are taken from the crate of `path`.
+fn uses(sources: &[Source], path: &syn::Path) -> Vec<ItemUse> {
+	let root = path.segments.first().map(|segment| &segment.ident);
+	let external = root.is_some_and(|root| {
+		path.leading_colon.is_some() || !["crate", "self", "super"].iter().any(|r| root == r)
+	});
+
+	let mut seen = HashSet::new();
+	sources
+		.iter()
+		.flat_map(|source| &source.file.items)
+		.filter_map(|item| match item {
+			Item::Use(item) if matches!(item.vis, Visibility::Inherited) => Some(item.clone()),
+			_ => None,
+		})
+		.filter(|item| match &item.tree {
+			UseTree::Path(tree) => tree.ident != "self" && tree.ident != "super",
+			_ => true,
+		})
+		.map(|mut item| {
+			if let (true, Some(root), UseTree::Path(tree)) = (external, root, &mut item.tree) {
+				if tree.ident == "crate" {
+					tree.ident = root.clone();
+				}
+			}
+			item.attrs.clear();
+			item
+		})
+		.filter(|item| seen.insert(quote!(#item).to_string()))
+		.collect()
+}
+
+/// Method of a trait, with its inputs named.
+struct MockMethod {
+	sig: Signature,
+	args: Vec<Ident>,
+	params: HashSet<Ident>,
+	iter_item: Option<(Type, Option<Lifetime>)>,
+}
+
+impl MockMethod {
+	fn new(sig: &Signature, params: &HashSet<Ident>) -> Self {
+		let mut sig = sig.clone();
+		let mut args = Vec::new();
+
+		for (index, input) in sig.inputs.iter_mut().enumerate() {
+			if let FnArg::Typed(input) = input {
+				let arg = match &*input.pat {
+					Pat::Ident(pat) => {
+						format_ident!("{}", pat.ident.to_string().trim_start_matches('_'))
+					}
+					_ => format_ident!("arg{}", index),
+				};
+				let arg = match arg.to_string().is_empty() {
+					true => format_ident!("arg{}", index),
+					false => arg,
+				};
+
+				*input.pat = parse_quote!(#arg);
+				args.push(arg);
+			}
+		}
+
+		let iter_item = match &sig.output {
+			ReturnType::Type(_, ty) => iterator_item(ty),
+			ReturnType::Default => None,
+		};
+
+		Self {
+			sig,
+			args,
+			params: params.clone(),
+			iter_item,
+		}
+	}
+
+	/// `mock_*` function registering the mock of the method.
+	fn register_fn(&self, trait_name: &Ident, prefixed: bool, register: &Ident) -> TokenStream {
+		let name = &self.sig.ident;
+		let (mock_name, allow) = match prefixed {
+			true => (
+				format_ident!("mock_{}_{}", trait_name, name),
+				Some(quote!(#[allow(non_snake_case)])),
+			),
+			false => (format_ident!("mock_{}", name), None),
+		};
+
+		let mut lifetimes = Vec::new();
+		let mut generics = Vec::<GenericParam>::new();
+		for param in &self.sig.generics.params {
+			match param {
+				GenericParam::Lifetime(param) => lifetimes.push(param.lifetime.clone()),
+				param => generics.push(param.clone()),
+			}
+		}
+
+		let mut inputs = Vec::new();
+		for input in &self.sig.inputs {
+			if let FnArg::Typed(input) = input {
+				let ty = match &*input.ty {
+					// An `impl Trait` input is a generic parameter of the mock function.
+					Type::ImplTrait(ty) => {
+						let param = format_ident!("Impl{}", generics.len());
+						let bounds = &ty.bounds;
+						generics.push(parse_quote!(#param: #bounds));
+						parse_quote!(#param)
+					}
+					ty => ty.clone(),
+				};
+				inputs.push(ty);
+			}
+		}
+
+		let output = match (&self.sig.output, &self.iter_item) {
+			(_, Some((item, lifetime))) => {
+				let lifetime = lifetime.clone().unwrap_or_else(|| parse_quote!('static));
+				quote!(-> MockIter<#lifetime, #item>)
+			}
+			(ReturnType::Type(_, ty), None) => quote!(-> #ty),
+			(ReturnType::Default, None) => quote!(),
+		};
+
+		let binder = (!lifetimes.is_empty()).then(|| quote!(for<#(#lifetimes),*>));
+		let where_clause = &self.sig.generics.where_clause;
+		let generics = (!generics.is_empty()).then(|| quote!(<#(#generics),*>));
+
+		let args = &self.args;
+		let closure = match args.len() {
+			0 => quote!(move |()| f()),
+			1 => quote!(f),
+			_ => quote!(move |(#(#args),*)| f(#(#args),*)),
+		};
+
+		let mut mock_fn: syn::ImplItemFn = parse_quote! {
+			#allow
+			pub fn #mock_name #generics(
+				f: impl #binder Fn(#(#inputs),*) #output + 'static,
+			) #where_clause {
+				#register!(#closure);
+			}
+		};
+
+		Substitute::register(&self.params).visit_impl_item_fn_mut(&mut mock_fn);
+		quote!(#mock_fn)
+	}
+
+	/// Method of the trait implementation, executing the mock.
+	fn impl_fn(&self, execute: &Ident) -> TokenStream {
+		let mut sig = self.sig.clone();
+		Substitute::implementation(&self.params).visit_signature_mut(&mut sig);
+
+		let args = &self.args;
+		let input = match args.len() {
+			0 => quote!(()),
+			1 => quote!(#(#args)*),
+			_ => quote!((#(#args),*)),
+		};
+
+		let body = match &self.iter_item {
+			Some((item, _)) => {
+				let mut item = item.clone();
+				Substitute::implementation(&self.params).visit_type_mut(&mut item);
+				quote! {
+					let iter: MockIter<#item> = #execute!(#input);
+					iter
+				}
+			}
+			None => quote!(#execute!(#input)),
+		};
+
+		quote! {
+			#sig {
+				#body
+			}
+		}
+	}
+}
+
+/// Item type and lifetime of an `impl Iterator<Item = ..> + 'a` type.
+fn iterator_item(ty: &Type) -> Option<(Type, Option<Lifetime>)> {
+	let Type::ImplTrait(ty) = ty else {
+		return None;
+	};
+
+	let mut item = None;
+	let mut lifetime = None;
+	for bound in &ty.bounds {
+		match bound {
+			TypeParamBound::Trait(bound) => {
+				let segment = bound.path.segments.last()?;
+				if segment.ident != "Iterator" {
+					return None;
+				}
+
+				if let PathArguments::AngleBracketed(args) = &segment.arguments {
+					item = args.args.iter().find_map(|arg| match arg {
+						GenericArgument::AssocType(assoc) if assoc.ident == "Item" => {
+							Some(assoc.ty.clone())
+						}
+						_ => None,
+					});
+				}
+			}
+			TypeParamBound::Lifetime(bound) => lifetime = Some(bound.clone()),
+			_ => (),
+		}
+	}
+
+	item.map(|item| (item, lifetime))
+}
+
+/// Replace the generic parameters of a trait, and optionally the associated
+/// types of `Self`, by the `Config` types of the pallet.
+struct Substitute<'a> {
+	root: Ident,
+	params: &'a HashSet<Ident>,
+	self_types: bool,
+}
+
+impl<'a> Substitute<'a> {
+	/// For the bounds of the `Config` types.
+	fn config(params: &'a HashSet<Ident>) -> Self {
+		Self {
+			root: format_ident!("Self"),
+			params,
+			self_types: false,
+		}
+	}
+
+	/// For the `mock_*` functions, implemented by the pallet, not the trait.
+	fn register(params: &'a HashSet<Ident>) -> Self {
+		Self {
+			root: format_ident!("T"),
+			params,
+			self_types: true,
+		}
+	}
+
+	/// For the methods of the trait implementation.
+	fn implementation(params: &'a HashSet<Ident>) -> Self {
+		Self {
+			root: format_ident!("T"),
+			params,
+			self_types: false,
+		}
+	}
+}
+
+impl VisitMut for Substitute<'_> {
+	fn visit_type_path_mut(&mut self, ty: &mut TypePath) {
+		visit_mut::visit_type_path_mut(self, ty);
+
+		let root = &self.root;
+		let segments = &ty.path.segments;
+
+		if let Some(qself) = &ty.qself {
+			// `<Self as Trait>::Type`
+			if self.self_types
+				&& matches!(&*qself.ty, Type::Path(path) if path.path.is_ident("Self"))
+			{
+				let rest = segments.iter().skip(qself.position);
+				*ty = parse_quote!(#root #(::#rest)*);
+			}
+		} else if ty.path.leading_colon.is_none() {
+			let first = &segments[0];
+			if self.self_types && segments.len() > 1 && first.ident == "Self" {
+				// `Self::Type`
+				let rest = segments.iter().skip(1);
+				*ty = parse_quote!(#root #(::#rest)*);
+			} else if segments.len() == 1
+				&& first.arguments.is_none()
+				&& self.params.contains(&first.ident)
+			{
+				// A parameter of the trait
+				let param = &first.ident;
+				*ty = parse_quote!(#root::#param);
+			}
+		}
+	}
+}
+
+/// Adapt the `prettyplease` output to the layout of the repository: tabs
+/// instead of spaces, a blank line between items, and closures inside the
+/// `register_call!()` macros written as they would be outside.
+fn layout(source: &str) -> String {
+	const ITEMS: [&str; 6] = ["#[", "pub ", "impl", "fn ", "type ", "use "];
+
+	let mut output = String::new();
+	let mut previous: Option<(usize, &str)> = None;
+
+	for line in source.lines() {
+		let spaces = line.len() - line.trim_start_matches(' ').len();
+		let (indent, code) = (spaces / 4, &line[spaces / 4 * 4..]);
+
+		let item = ITEMS.iter().any(|start| code.starts_with(start));
+		if let Some((previous_indent, previous_code)) = previous {
+			let item_end = previous_code.ends_with('}') || previous_code.ends_with(';');
+			let uses = previous_code.starts_with("use ") && code.starts_with("use ");
+			if item && item_end && !uses && previous_indent == indent {
+				output.push('\n');
+			}
+		}
+
+		let code = match code.starts_with("register_call") {
+			true => code.replace("| (", "|(").replace(") |", ")|"),
+			false => code.into(),
+		};
+
+		output.push_str(&"\t".repeat(indent));
+		output.push_str(&code);
+		output.push('\n');
+		previous = Some((indent, line.trim_start()));
+	}
+
+	output
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn generate_from(source: &str, names: &[&str], instance: bool) -> Result<String, String> {
+		let sources = [Source {
+			name: "src/traits.rs".into(),
+			file: syn::parse_str(source).unwrap(),
+		}];
+
+		let names = names
+			.iter()
+			.map(|name| name.to_string())
+			.collect::<Vec<_>>();
+		let options = Options {
+			path: parse_quote!(crate),
+			instance,
+		};
+
+		generate(&sources, &names, &options)
+	}
+
+	#[test]
+	fn pallet() {
+		let source = r#"
+			use frame_support::traits::Get;
+
+			pub trait TraitA {
+				fn foo(p1: String, p2: Option<u64>);
+				fn bar(_p1: u64) -> Result<(), String>;
+				fn qux() -> bool;
+				fn with_default() -> bool {
+					true
+				}
+			}
+		"#;
+
+		assert_eq!(
+			generate_from(source, &[], false).unwrap(),
+			r#"// @generated by `mock-builder`, do not edit.
+
+//! Mock pallet for `TraitA`, generated from `src/traits.rs`.
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use mock_builder::{execute_call, register_call};
+	use frame_support::traits::Get;
+	use crate::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	type CallIds<T: Config> = StorageMap<_, _, String, mock_builder::CallId>;
+
+	impl<T: Config> Pallet<T> {
+		pub fn mock_foo(f: impl Fn(String, Option<u64>) + 'static) {
+			register_call!(move |(p1, p2)| f(p1, p2));
+		}
+
+		pub fn mock_bar(f: impl Fn(u64) -> Result<(), String> + 'static) {
+			register_call!(f);
+		}
+
+		pub fn mock_qux(f: impl Fn() -> bool + 'static) {
+			register_call!(move |()| f());
+		}
+	}
+
+	impl<T: Config> TraitA for Pallet<T> {
+		fn foo(p1: String, p2: Option<u64>) {
+			execute_call!((p1, p2))
+		}
+
+		fn bar(p1: u64) -> Result<(), String> {
+			execute_call!(p1)
+		}
+
+		fn qux() -> bool {
+			execute_call!(())
+		}
+	}
+}
+"#
+		);
+	}
+
+	#[test]
+	fn same_method_names_prefixed() {
+		let source = r#"
+			pub trait TraitA {
+				fn same_name(p1: bool) -> usize;
+			}
+
+			pub trait TraitB {
+				fn same_name(p1: i32) -> bool;
+			}
+		"#;
+
+		let pallet = generate_from(source, &["TraitA", "TraitB"], false).unwrap();
+
+		assert!(pallet.contains("#[allow(non_snake_case)]\n\t\tpub fn mock_TraitA_same_name(f: impl Fn(bool) -> usize + 'static)"));
+		assert!(pallet.contains("pub fn mock_TraitB_same_name(f: impl Fn(i32) -> bool + 'static)"));
+	}
+
+	#[test]
+	fn config_types() {
+		let source = r#"
+			pub trait Currency<AccountId> {
+				type Balance: Copy + From<u64>;
+
+				fn balance(who: &AccountId) -> Self::Balance;
+				fn convert(amount: <Self as Currency<AccountId>>::Balance) -> u128;
+			}
+		"#;
+
+		let pallet = generate_from(source, &[], false).unwrap();
+
+		assert!(pallet.contains("type AccountId;\n\n\t\ttype Balance: Copy + From<u64>;"));
+		assert!(pallet
+			.contains("pub fn mock_balance(f: impl Fn(&T::AccountId) -> T::Balance + 'static)"));
+		assert!(pallet.contains("pub fn mock_convert(f: impl Fn(T::Balance) -> u128 + 'static)"));
+		assert!(pallet.contains("impl<T: Config> Currency<T::AccountId> for Pallet<T> {"));
+		assert!(pallet.contains("type Balance = T::Balance;"));
+		assert!(pallet.contains("fn balance(who: &T::AccountId) -> Self::Balance {"));
+	}
+
+	#[test]
+	fn generic_methods() {
+		let source = r#"
+			pub trait TraitB {
+				fn generic_input<A: Into<i32>>(a: A, b: impl Into<u32>) -> usize;
+				fn references<'a>(buf: &'a mut Vec<u8>, value: &u8) -> &'a u8;
+			}
+		"#;
+
+		let pallet = generate_from(source, &[], false).unwrap();
+
+		assert!(pallet.contains(
+			"pub fn mock_generic_input<A: Into<i32>, Impl1: Into<u32>>(\n\t\t\tf: impl Fn(A, Impl1) -> usize + 'static,\n\t\t)"
+		));
+		assert!(pallet.contains("f: impl for<'a> Fn(&'a mut Vec<u8>, &u8) -> &'a u8 + 'static"));
+		assert!(
+			pallet.contains("fn generic_input<A: Into<i32>>(a: A, b: impl Into<u32>) -> usize {")
+		);
+	}
+
+	#[test]
+	fn iterators() {
+		let source = r#"
+			pub trait TraitB {
+				fn iter() -> impl Iterator<Item = u32>;
+				fn iter_reference(values: &[u32]) -> impl Iterator<Item = &u32> + '_;
+			}
+		"#;
+
+		let pallet = generate_from(source, &[], false).unwrap();
+
+		assert!(pallet.contains("use mock_builder::{execute_call, register_call, MockIter};"));
+		assert!(pallet.contains("f: impl Fn() -> MockIter<'static, u32> + 'static"));
+		assert!(pallet.contains("f: impl Fn(&[u32]) -> MockIter<'_, &u32> + 'static"));
+		assert!(pallet.contains("let iter: MockIter<&u32> = execute_call!(values);\n\t\t\titer"));
+	}
+
+	#[test]
+	fn instance() {
+		let source = r#"
+			pub trait TraitA {
+				fn foo() -> bool;
+			}
+		"#;
+
+		let pallet = generate_from(source, &[], true).unwrap();
+
+		assert!(pallet.contains("pub trait Config<I: 'static = ()>: frame_system::Config {}"));
+		assert!(pallet.contains("pub struct Pallet<T, I = ()>(_);"));
+		assert!(pallet.contains("type CallIds<T: Config<I>, I: 'static = ()> ="));
+		assert!(pallet.contains("impl<T: Config<I>, I: 'static> TraitA for Pallet<T, I> {"));
+		assert!(pallet.contains("register_call_instance!(move |()| f());"));
+		assert!(pallet.contains("execute_call_instance!(())"));
+	}
+
+	#[test]
+	fn crate_uses_from_external_path() {
+		let source = r#"
+			use crate::types::Balance;
+			use super::Other;
+			pub use pallet::*;
+
+			pub trait TraitA {
+				fn foo() -> Balance;
+			}
+		"#;
+
+		let sources = [Source {
+			name: "src/lib.rs".into(),
+			file: syn::parse_str(source).unwrap(),
+		}];
+		let options = Options {
+			path: parse_quote!(my_pallet),
+			instance: false,
+		};
+
+		let pallet = generate(&sources, &[], &options).unwrap();
+
+		assert!(pallet.contains("use my_pallet::types::Balance;\n\tuse my_pallet::*;"));
+		assert!(!pallet.contains("Other"));
+		assert!(!pallet.contains("pub use"));
+	}
+
+	#[test]
+	fn errors() {
+		assert_eq!(
+			generate_from("pub trait A { fn foo(); }", &["B"], false),
+			Err("trait `B` not found".into())
+		);
+		assert_eq!(
+			generate_from("pub trait A { fn foo(&self); }", &[], false),
+			Err("`A::foo` has a `self` receiver, which a mock pallet can not implement. Use `mock_builder::mock_object!()` instead".into())
+		);
+		assert_eq!(
+			generate_from("pub trait A<'a> { fn foo(); }", &[], false),
+			Err("`A` has lifetime or const parameters, only type parameters are supported".into())
+		);
+		assert_eq!(
+			generate_from("pub trait A { const B: u32; }", &[], false),
+			Err("`A::B` is an associated constant, which is not supported".into())
+		);
+	}
+}
@@ -0,0 +1,127 @@
+//! `mock-builder` command line tool, generating the source of a *mock pallet*
+//! from the traits found in Rust source files:
+//!
+//! ```sh
+//! mock-builder --trait TraitA --trait TraitB -o src/mock/pallet_mock_traits.rs src/traits.rs
+//! ```
+//!
+//! The generated module contains the `CallIds` storage, the `mock_*()`
+//! functions and the trait implementations that would be written by hand
+//! following the `mock-builder` documentation.
+
+use std::{fs, process::ExitCode};
+
+mod generate;
+
+use generate::{generate, Options, Source};
+
+const USAGE: &str = "\
+Generate the source of a mock pallet from the traits of Rust source files.
+
+Usage: mock-builder [OPTIONS] <FILE>...
+
+Arguments:
+  <FILE>...  Rust source files where the traits are defined
+
+Options:
+  -t, --trait <NAME>   Trait to mock. Can be repeated. All the traits found are mocked if not given
+  -p, --path <PATH>    Path of the module defining the traits, imported by the mock [default: crate]
+  -i, --instance       Generate an instantiable mock pallet
+  -o, --output <FILE>  File to write the mock pallet to [default: stdout]
+  -h, --help           Print help";
+
+struct Args {
+	files: Vec<String>,
+	traits: Vec<String>,
+	path: String,
+	instance: bool,
+	output: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+	let mut parsed = Args {
+		files: Vec::new(),
+		traits: Vec::new(),
+		path: "crate".into(),
+		instance: false,
+		output: None,
+	};
+
+	while let Some(arg) = args.next() {
+		let mut value = || {
+			args.next()
+				.ok_or(format!("a value is required for `{arg}`"))
+		};
+
+		match arg.as_str() {
+			"-h" | "--help" => return Ok(None),
+			"-t" | "--trait" => parsed.traits.push(value()?),
+			"-p" | "--path" => parsed.path = value()?,
+			"-i" | "--instance" => parsed.instance = true,
+			"-o" | "--output" => parsed.output = Some(value()?),
+			_ if arg.starts_with('-') => return Err(format!("unexpected argument `{arg}`")),
+			_ => parsed.files.push(arg),
+		}
+	}
+
+	if parsed.files.is_empty() {
+		return Err("at least a source file is required".into());
+	}
+
+	Ok(Some(parsed))
+}
+
+fn run(args: Args) -> Result<(), String> {
+	let sources = args
+		.files
+		.iter()
+		.map(|name| {
+			let content =
+				fs::read_to_string(name).map_err(|err| format!("can not read `{name}`: {err}"))?;
+			let file = syn::parse_file(&content)
+				.map_err(|err| format!("can not parse `{name}`: {err}"))?;
+
+			Ok(Source {
+				name: name.clone(),
+				file,
+			})
+		})
+		.collect::<Result<Vec<_>, String>>()?;
+
+	let options = Options {
+		path: syn::parse_str(&args.path)
+			.map_err(|err| format!("invalid path `{}`: {err}", args.path))?,
+		instance: args.instance,
+	};
+
+	let pallet = generate(&sources, &args.traits, &options)?;
+
+	match args.output {
+		Some(output) => {
+			fs::write(&output, pallet).map_err(|err| format!("can not write `{output}`: {err}"))
+		}
+		None => {
+			print!("{pallet}");
+			Ok(())
+		}
+	}
+}
+
+fn main() -> ExitCode {
+	let result = match parse_args(std::env::args().skip(1)) {
+		Ok(Some(args)) => run(args),
+		Ok(None) => {
+			println!("{USAGE}");
+			Ok(())
+		}
+		Err(err) => Err(format!("{err}\n\n{USAGE}")),
+	};
+
+	match result {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(err) => {
+			eprintln!("error: {err}");
+			ExitCode::FAILURE
+		}
+	}
+}
@@ -0,0 +1,53 @@
+//! Procedural macros for `mock-builder`.
+//! They're expected to be used through the `mock-builder` crate re-exports.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+	parse::Parser, parse_macro_input, punctuated::Punctuated, ItemFn, MetaNameValue, ReturnType,
+	Token,
+};
+
+mod runtime;
+mod test;
+
+/// Run a test inside externalities built for a runtime.
+/// See `mock_builder::test` for the documentation.
+#[proc_macro_attribute]
+pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
+	let item = parse_macro_input!(item as ItemFn);
+
+	Punctuated::<MetaNameValue, Token![,]>::parse_terminated
+		.parse(attr)
+		.and_then(test::Args::try_from)
+		.map(|args| test::expand(args, item))
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Declare a mock runtime with the pallets under test and their mocks.
+/// See `mock_builder::mock_runtime` for the documentation.
+#[proc_macro]
+pub fn mock_runtime(input: TokenStream) -> TokenStream {
+	let runtime = parse_macro_input!(input as runtime::MockRuntime);
+	runtime::expand(runtime)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Return type of a function as a type expression.
+fn output_type(output: &ReturnType) -> TokenStream2 {
+	match output {
+		ReturnType::Default => quote!(()),
+		ReturnType::Type(_, ty) => quote!(#ty),
+	}
+}
+
+/// Name of a `name = value` argument.
+fn arg_name(arg: &MetaNameValue) -> syn::Result<String> {
+	arg.path
+		.get_ident()
+		.map(|ident| ident.to_string())
+		.ok_or_else(|| syn::Error::new_spanned(&arg.path, "expected an argument name"))
+}
@@ -0,0 +1,221 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+	braced,
+	parse::{Parse, ParseStream},
+	token, Ident, ImplItemType, Path, Token,
+};
+
+const SYSTEM: &str = "frame_system";
+
+/// `pub struct Name; System: frame_system { <types> } <pallets>`
+pub struct MockRuntime {
+	name: Ident,
+	system: Pallet,
+	pallets: Vec<Pallet>,
+}
+
+/// `Name: path { <config items> }`. Without config items, the `Config`
+/// implementation of the pallet must be written outside of the macro.
+struct Pallet {
+	name: Ident,
+	path: Path,
+	config: Option<Vec<ConfigItem>>,
+}
+
+enum ConfigItem {
+	/// `type Name = Type;`
+	Type(ImplItemType),
+
+	/// `type Name => path { <config items> };`
+	Mock {
+		name: Ident,
+		path: Path,
+		config: Vec<ImplItemType>,
+	},
+}
+
+impl Parse for MockRuntime {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		// As `construct_runtime!`, the runtime must be public.
+		input.parse::<Token![pub]>()?;
+		input.parse::<Token![struct]>()?;
+		let name = input.parse()?;
+		input.parse::<Token![;]>()?;
+
+		let system = input.parse::<Pallet>()?;
+		if system
+			.path
+			.segments
+			.last()
+			.map_or(true, |segment| segment.ident != SYSTEM)
+		{
+			return Err(syn::Error::new_spanned(
+				&system.path,
+				"expected `frame_system` as the first pallet",
+			));
+		}
+		input.parse::<Option<Token![,]>>()?;
+
+		let mut pallets = Vec::new();
+		while !input.is_empty() {
+			pallets.push(input.parse()?);
+			input.parse::<Option<Token![,]>>()?;
+		}
+
+		Ok(Self {
+			name,
+			system,
+			pallets,
+		})
+	}
+}
+
+impl Parse for Pallet {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let name = input.parse()?;
+		input.parse::<Token![:]>()?;
+		let path = Path::parse_mod_style(input)?;
+
+		let config = match input.peek(token::Brace) {
+			true => {
+				let content;
+				braced!(content in input);
+
+				let mut items = Vec::new();
+				while !content.is_empty() {
+					items.push(content.parse()?);
+				}
+				Some(items)
+			}
+			false => None,
+		};
+
+		Ok(Self { name, path, config })
+	}
+}
+
+impl Parse for ConfigItem {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		if !input.peek2(Ident) || !input.peek3(Token![=>]) {
+			return input.parse().map(ConfigItem::Type);
+		}
+
+		input.parse::<Token![type]>()?;
+		let name = input.parse()?;
+		input.parse::<Token![=>]>()?;
+		let path = Path::parse_mod_style(input)?;
+		let config = match input.peek(token::Brace) {
+			true => parse_types(input)?,
+			false => Vec::new(),
+		};
+		input.parse::<Token![;]>()?;
+
+		Ok(ConfigItem::Mock { name, path, config })
+	}
+}
+
+/// Parse `{ type Name = Type; .. }`
+fn parse_types(input: ParseStream) -> syn::Result<Vec<ImplItemType>> {
+	let content;
+	braced!(content in input);
+
+	let mut items = Vec::new();
+	while !content.is_empty() {
+		items.push(content.parse()?);
+	}
+
+	Ok(items)
+}
+
+pub fn expand(runtime: MockRuntime) -> syn::Result<TokenStream2> {
+	let MockRuntime {
+		name: runtime,
+		system,
+		pallets,
+	} = runtime;
+
+	let Pallet {
+		name: system_name,
+		path: system_path,
+		config: system_config,
+	} = system;
+
+	// The system configuration only extends the test defaults, so it can't
+	// bind mock pallets.
+	let system_items = system_config
+		.unwrap_or_default()
+		.into_iter()
+		.map(|item| match item {
+			ConfigItem::Type(item) => Ok(item),
+			ConfigItem::Mock { path, .. } => Err(syn::Error::new_spanned(
+				path,
+				"mock pallets can't be bound in the `frame_system` configuration",
+			)),
+		})
+		.collect::<syn::Result<Vec<_>>>()?;
+
+	let mut entries = vec![quote!(#system_name: #system_path)];
+	let mut impls = Vec::new();
+	let mut mocks = Vec::new();
+
+	for Pallet { name, path, config } in pallets {
+		entries.push(quote!(#name: #path));
+
+		let Some(config) = config else {
+			continue;
+		};
+
+		let mut items = Vec::new();
+		for item in config {
+			match item {
+				ConfigItem::Type(item) => items.push(quote!(#item)),
+				ConfigItem::Mock { name, path, config } => {
+					// A binding adds the mock pallet and implements its `Config`.
+					let mock_path = quote!(#path).to_string();
+					if mocks.contains(&mock_path) {
+						return Err(syn::Error::new_spanned(
+							path,
+							"mock pallet already bound to another type",
+						));
+					}
+					mocks.push(mock_path);
+
+					let mock = format_ident!("Mock{}", name);
+
+					entries.push(quote!(#mock: #path));
+					items.push(quote!(type #name = #mock;));
+					impls.push(quote! {
+						impl #path::Config for #runtime {
+							#(#config)*
+						}
+					});
+				}
+			}
+		}
+
+		impls.push(quote! {
+			impl #path::Config for #runtime {
+				#(#items)*
+			}
+		});
+	}
+
+	Ok(quote! {
+		::frame_support::construct_runtime!(
+			pub struct #runtime {
+				#(#entries,)*
+			}
+		);
+
+		#[::frame_support::derive_impl(
+			#system_path::config_preludes::TestDefaultConfig as #system_path::DefaultConfig
+		)]
+		impl #system_path::Config for #runtime {
+			type Block = #system_path::mocking::MockBlock<#runtime>;
+			#(#system_items)*
+		}
+
+		#(#impls)*
+	})
+}
@@ -0,0 +1,142 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{punctuated::Punctuated, Expr, ItemFn, MetaNameValue, Token};
+
+use super::{arg_name, output_type};
+
+pub struct Args {
+	runtime: Expr,
+	genesis: Option<Expr>,
+	block: Option<Expr>,
+	strict: Option<Expr>,
+	golden: Option<Expr>,
+}
+
+impl TryFrom<Punctuated<MetaNameValue, Token![,]>> for Args {
+	type Error = syn::Error;
+
+	fn try_from(args: Punctuated<MetaNameValue, Token![,]>) -> syn::Result<Self> {
+		let mut runtime = None;
+		let mut genesis = None;
+		let mut block = None;
+		let mut strict = None;
+		let mut golden = None;
+
+		for arg in args {
+			match arg_name(&arg)?.as_str() {
+				"runtime" => runtime = Some(arg.value),
+				"genesis" => genesis = Some(arg.value),
+				"block" => block = Some(arg.value),
+				"strict" => strict = Some(arg.value),
+				"golden" => golden = Some(arg.value),
+				_ => {
+					return Err(syn::Error::new_spanned(
+						&arg.path,
+						"unknown argument, expected `runtime`, `genesis`, `block`, `strict` or `golden`",
+					))
+				}
+			}
+		}
+
+		Ok(Self {
+			runtime: runtime.ok_or_else(|| {
+				syn::Error::new(
+					proc_macro2::Span::call_site(),
+					"missing argument `runtime = <Runtime>`",
+				)
+			})?,
+			genesis,
+			block,
+			strict,
+			golden,
+		})
+	}
+}
+
+pub fn expand(args: Args, item: ItemFn) -> TokenStream2 {
+	let Args {
+		runtime,
+		genesis,
+		block,
+		strict,
+		golden,
+	} = args;
+
+	let ItemFn {
+		attrs,
+		vis,
+		sig,
+		block: body,
+	} = item;
+
+	let output = output_type(&sig.output);
+
+	// A genesis storage has no block number set, so we start at block 1 to
+	// have events deposited, as `frame_system::Pallet::externalities()` does.
+	let block = block.or_else(|| genesis.as_ref().map(|_| syn::parse_quote!(1)));
+
+	// The dependencies are reached through `mock-builder`, so the test crate
+	// doesn't need to depend on them.
+	let private = quote!(::mock_builder::__private);
+
+	let ext = match genesis {
+		Some(genesis) => quote! {
+			#private::sp_io::TestExternalities::new(
+				#private::sp_runtime::BuildStorage::build_storage(&#genesis)
+					.expect("genesis storage can be built"),
+			)
+		},
+		None => quote! {
+			#private::frame_system::Pallet::<#runtime>::externalities()
+		},
+	};
+
+	let set_block = block.map(|block| {
+		quote! {
+			#private::frame_system::Pallet::<#runtime>::set_block_number(#block);
+		}
+	});
+
+	let enable_strict = strict.map(|strict| {
+		quote! {
+			if #strict {
+				::mock_builder::strict::enable();
+			}
+		}
+	});
+
+	// Golden files are relative to the package of the test, wherever it runs.
+	let (start_golden, assert_golden) = match golden {
+		Some(golden) => (
+			Some(quote!(::mock_builder::golden::start();)),
+			Some(quote! {
+				::mock_builder::golden::assert_golden(
+					::std::path::Path::new(::core::env!("CARGO_MANIFEST_DIR")).join(#golden),
+				);
+			}),
+		),
+		None => (None, None),
+	};
+
+	quote! {
+		#[test]
+		#(#attrs)*
+		#vis #sig {
+			::mock_builder::trace::init();
+			#enable_strict
+			#start_golden
+
+			let mut ext = #ext;
+			let output = ext.execute_with(|| -> #output {
+				#set_block
+				#body
+			});
+
+			#assert_golden
+			::mock_builder::verify();
+			::mock_builder::clear();
+
+			output
+		}
+	}
+}
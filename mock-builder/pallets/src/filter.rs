@@ -0,0 +1,115 @@
+//! Mock pallet for the `Contains` and `Get` traits, and a mock type for the
+//! `InstanceFilter` trait, i.e. a proxy type. They're generic over the filtered
+//! or returned type, so the same pallet can be used as `BaseCallFilter`, as a
+//! `ProxyType` or as any constant:
+//!
+//! ```ignore
+//! impl frame_system::Config for Runtime {
+//!     type BaseCallFilter = MockFilter;
+//! }
+//!
+//! impl pallet_proxy::Config for Runtime {
+//!     type MaxProxies = MockFilter;
+//!     type ProxyType = MockInstanceFilter<Runtime>;
+//! }
+//! ```
+//!
+//! As with any other mock, the behavior can change in the middle of a test:
+//!
+//! ```ignore
+//! MockFilter::mock_contains(|call: &RuntimeCall| !matches!(call, RuntimeCall::Proxy(_)));
+//! MockFilter::mock_get(|| 32u32);
+//! MockFilter::mock_filter(|proxy_type, _: &RuntimeCall| proxy_type.0 == ANY);
+//! ```
+//!
+//! The type of the closure must match exactly the type of the trait, so
+//! literals usually need a suffix. Use an instance for each constant of the
+//! same type.
+
+pub use pallet::MockInstanceFilter;
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet {
+	use alloc::string::String;
+
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Contains, InstanceFilter},
+		CloneNoBound, DefaultNoBound, EqNoBound, OrdNoBound, PartialEqNoBound, PartialOrdNoBound,
+		RuntimeDebugNoBound,
+	};
+	use mock_builder::{execute_call_instance, register_call_instance};
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(_);
+
+	#[pallet::storage]
+	type CallIds<T: Config<I>, I: 'static = ()> = StorageMap<_, _, String, mock_builder::CallId>;
+
+	/// Mock type for `InstanceFilter`, identified by its `u8`. The behavior of
+	/// all identifiers is registered with [`Pallet::mock_filter()`].
+	#[derive(
+		Encode,
+		Decode,
+		MaxEncodedLen,
+		TypeInfo,
+		CloneNoBound,
+		PartialEqNoBound,
+		EqNoBound,
+		PartialOrdNoBound,
+		OrdNoBound,
+		DefaultNoBound,
+		RuntimeDebugNoBound,
+	)]
+	#[scale_info(skip_type_params(T, I))]
+	pub struct MockInstanceFilter<T, I = ()>(pub u8, PhantomData<fn() -> (T, I)>);
+
+	impl<T, I> Copy for MockInstanceFilter<T, I> {}
+
+	impl<T, I> MockInstanceFilter<T, I> {
+		pub fn new(id: u8) -> Self {
+			Self(id, PhantomData)
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		pub fn mock_contains<C: 'static>(f: impl Fn(&C) -> bool + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_get<V: 'static>(f: impl Fn() -> V + 'static) {
+			register_call_instance!(move |()| f());
+		}
+
+		pub fn mock_filter<C: 'static>(
+			f: impl Fn(&MockInstanceFilter<T, I>, &C) -> bool + 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		fn filter<C>(instance: &MockInstanceFilter<T, I>, c: &C) -> bool {
+			execute_call_instance!((instance, c))
+		}
+	}
+
+	impl<T: Config<I>, I: 'static, C> Contains<C> for Pallet<T, I> {
+		fn contains(c: &C) -> bool {
+			execute_call_instance!(c)
+		}
+	}
+
+	impl<T: Config<I>, I: 'static, V> Get<V> for Pallet<T, I> {
+		fn get() -> V {
+			execute_call_instance!(())
+		}
+	}
+
+	impl<T: Config<I>, I: 'static, C> InstanceFilter<C> for MockInstanceFilter<T, I> {
+		fn filter(&self, c: &C) -> bool {
+			Pallet::<T, I>::filter(self, c)
+		}
+	}
+}
@@ -0,0 +1,354 @@
+//! Mock pallet for the `fungible` traits: `Inspect`, `Unbalanced`, `Mutate`
+//! and their `hold` and `freeze` counterparts.
+//!
+//! Each required method can be mocked through its `mock_<Trait>_<method>()`
+//! function, i.e. `mock_Inspect_balance()`. The provided methods, like
+//! `Mutate::transfer()` or `hold::Mutate::hold()`, are built on top of them.
+//!
+//! Calling [`Pallet::use_ledger()`](pallet::Pallet::use_ledger) registers all
+//! of them backed by a ledger in the pallet storage, obtaining realistic
+//! balances without configuring `pallet_balances`:
+//!
+//! ```ignore
+//! MockFungible::use_ledger();
+//! MockFungible::mock_Inspect_minimum_balance(|| 10);
+//!
+//! assert_ok!(MockFungible::mint_into(&ALICE, 100));
+//! assert_ok!(MockFungible::hold(&HoldReason::Staking, &ALICE, 30));
+//! ```
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet {
+	use alloc::string::String;
+
+	use frame_support::{
+		pallet_prelude::*,
+		traits::tokens::{
+			fungible::{freeze, hold, Dust, Inspect, Mutate, Unbalanced},
+			DepositConsequence, Fortitude, Preservation, Provenance, WithdrawConsequence,
+		},
+	};
+	use mock_builder::{execute_call_instance, register_call_instance};
+	use sp_runtime::traits::Zero;
+
+	use crate::ledger::AccountData;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		type Balance: frame_support::traits::tokens::Balance;
+		type HoldReason: Parameter + 'static;
+		type FreezeId: Parameter + 'static;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(_);
+
+	#[pallet::storage]
+	type CallIds<T: Config<I>, I: 'static = ()> = StorageMap<_, _, String, mock_builder::CallId>;
+
+	#[pallet::storage]
+	type TotalIssuance<T: Config<I>, I: 'static = ()> = StorageValue<_, T::Balance, ValueQuery>;
+
+	#[pallet::storage]
+	type Accounts<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		_,
+		T::AccountId,
+		AccountData<T::Balance, T::HoldReason, T::FreezeId>,
+		ValueQuery,
+	>;
+
+	#[allow(non_snake_case)]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Register all methods backed by a ledger in the pallet storage,
+		/// without existential deposit, as [lax](mock_builder::strict) mocks.
+		pub fn use_ledger() {
+			mock_builder::strict::lax(|| {
+				Self::mock_Inspect_total_issuance(TotalIssuance::<T, I>::get);
+				Self::mock_Inspect_minimum_balance(T::Balance::zero);
+				Self::mock_Inspect_total_balance(|who| Accounts::<T, I>::get(who).total());
+				Self::mock_Inspect_balance(|who| Accounts::<T, I>::get(who).free);
+				Self::mock_Inspect_reducible_balance(|who, _, force| {
+					Accounts::<T, I>::get(who).reducible(force)
+				});
+				Self::mock_Inspect_can_deposit(|_, amount, _| {
+					match TotalIssuance::<T, I>::get().checked_add(&amount) {
+						Some(_) => DepositConsequence::Success,
+						None => DepositConsequence::Overflow,
+					}
+				});
+				Self::mock_Inspect_can_withdraw(|who, amount| {
+					Accounts::<T, I>::get(who).can_withdraw(amount)
+				});
+
+				Self::mock_Unbalanced_handle_dust(|_| ());
+				Self::mock_Unbalanced_write_balance(|who, amount| {
+					Accounts::<T, I>::mutate(who, |account| account.free = amount);
+					Ok(None)
+				});
+				Self::mock_Unbalanced_set_total_issuance(TotalIssuance::<T, I>::put);
+
+				Self::mock_Inspect_total_balance_on_hold(|who| {
+					Accounts::<T, I>::get(who).total_on_hold()
+				});
+				Self::mock_Inspect_reducible_total_balance_on_hold(|who, _| {
+					Accounts::<T, I>::get(who).total_on_hold()
+				});
+				Self::mock_Inspect_balance_on_hold(|reason, who| {
+					Accounts::<T, I>::get(who).on_hold(reason)
+				});
+				Self::mock_Inspect_hold_available(|_, _| true);
+				Self::mock_Unbalanced_set_balance_on_hold(|reason, who, amount| {
+					Accounts::<T, I>::mutate(who, |account| account.set_on_hold(reason, amount));
+					Ok(())
+				});
+
+				Self::mock_Inspect_balance_frozen(|id, who| {
+					Accounts::<T, I>::get(who).frozen_by(id)
+				});
+				Self::mock_Inspect_can_freeze(|_, _| true);
+				Self::mock_Mutate_set_freeze(|id, who, amount| {
+					Accounts::<T, I>::mutate(who, |account| account.set_frozen(id, amount));
+					Ok(())
+				});
+				Self::mock_Mutate_extend_freeze(|id, who, amount| {
+					Accounts::<T, I>::mutate(who, |account| {
+						account.set_frozen(id, account.frozen_by(id).max(amount))
+					});
+					Ok(())
+				});
+				Self::mock_Mutate_thaw(|id, who| {
+					Accounts::<T, I>::mutate(who, |account| account.set_frozen(id, Zero::zero()));
+					Ok(())
+				});
+			})
+		}
+
+		pub fn mock_Inspect_total_issuance(f: impl Fn() -> T::Balance + 'static) {
+			register_call_instance!(move |()| f());
+		}
+
+		pub fn mock_Inspect_minimum_balance(f: impl Fn() -> T::Balance + 'static) {
+			register_call_instance!(move |()| f());
+		}
+
+		pub fn mock_Inspect_total_balance(f: impl Fn(&T::AccountId) -> T::Balance + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_Inspect_balance(f: impl Fn(&T::AccountId) -> T::Balance + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_Inspect_reducible_balance(
+			f: impl Fn(&T::AccountId, Preservation, Fortitude) -> T::Balance + 'static,
+		) {
+			register_call_instance!(move |(a, b, c)| f(a, b, c));
+		}
+
+		pub fn mock_Inspect_can_deposit(
+			f: impl Fn(&T::AccountId, T::Balance, Provenance) -> DepositConsequence + 'static,
+		) {
+			register_call_instance!(move |(a, b, c)| f(a, b, c));
+		}
+
+		pub fn mock_Inspect_can_withdraw(
+			f: impl Fn(&T::AccountId, T::Balance) -> WithdrawConsequence<T::Balance> + 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_Unbalanced_handle_dust(f: impl Fn(Dust<T::AccountId, Self>) + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_Unbalanced_write_balance(
+			f: impl Fn(&T::AccountId, T::Balance) -> Result<Option<T::Balance>, DispatchError> + 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_Unbalanced_set_total_issuance(f: impl Fn(T::Balance) + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_Inspect_total_balance_on_hold(
+			f: impl Fn(&T::AccountId) -> T::Balance + 'static,
+		) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_Inspect_reducible_total_balance_on_hold(
+			f: impl Fn(&T::AccountId, Fortitude) -> T::Balance + 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_Inspect_balance_on_hold(
+			f: impl Fn(&T::HoldReason, &T::AccountId) -> T::Balance + 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_Inspect_hold_available(
+			f: impl Fn(&T::HoldReason, &T::AccountId) -> bool + 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_Unbalanced_set_balance_on_hold(
+			f: impl Fn(&T::HoldReason, &T::AccountId, T::Balance) -> DispatchResult + 'static,
+		) {
+			register_call_instance!(move |(a, b, c)| f(a, b, c));
+		}
+
+		pub fn mock_Inspect_balance_frozen(
+			f: impl Fn(&T::FreezeId, &T::AccountId) -> T::Balance + 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_Inspect_can_freeze(f: impl Fn(&T::FreezeId, &T::AccountId) -> bool + 'static) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_Mutate_set_freeze(
+			f: impl Fn(&T::FreezeId, &T::AccountId, T::Balance) -> DispatchResult + 'static,
+		) {
+			register_call_instance!(move |(a, b, c)| f(a, b, c));
+		}
+
+		pub fn mock_Mutate_extend_freeze(
+			f: impl Fn(&T::FreezeId, &T::AccountId, T::Balance) -> DispatchResult + 'static,
+		) {
+			register_call_instance!(move |(a, b, c)| f(a, b, c));
+		}
+
+		pub fn mock_Mutate_thaw(
+			f: impl Fn(&T::FreezeId, &T::AccountId) -> DispatchResult + 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Inspect<T::AccountId> for Pallet<T, I> {
+		type Balance = T::Balance;
+
+		fn total_issuance() -> T::Balance {
+			execute_call_instance!(())
+		}
+
+		fn minimum_balance() -> T::Balance {
+			execute_call_instance!(())
+		}
+
+		fn total_balance(who: &T::AccountId) -> T::Balance {
+			execute_call_instance!(who)
+		}
+
+		fn balance(who: &T::AccountId) -> T::Balance {
+			execute_call_instance!(who)
+		}
+
+		fn reducible_balance(
+			who: &T::AccountId,
+			preservation: Preservation,
+			force: Fortitude,
+		) -> T::Balance {
+			execute_call_instance!((who, preservation, force))
+		}
+
+		fn can_deposit(
+			who: &T::AccountId,
+			amount: T::Balance,
+			provenance: Provenance,
+		) -> DepositConsequence {
+			execute_call_instance!((who, amount, provenance))
+		}
+
+		fn can_withdraw(who: &T::AccountId, amount: T::Balance) -> WithdrawConsequence<T::Balance> {
+			execute_call_instance!((who, amount))
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Unbalanced<T::AccountId> for Pallet<T, I> {
+		fn handle_dust(dust: Dust<T::AccountId, Self>) {
+			execute_call_instance!(dust)
+		}
+
+		fn write_balance(
+			who: &T::AccountId,
+			amount: T::Balance,
+		) -> Result<Option<T::Balance>, DispatchError> {
+			execute_call_instance!((who, amount))
+		}
+
+		fn set_total_issuance(amount: T::Balance) {
+			execute_call_instance!(amount)
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Mutate<T::AccountId> for Pallet<T, I> {}
+
+	impl<T: Config<I>, I: 'static> hold::Inspect<T::AccountId> for Pallet<T, I> {
+		type Reason = T::HoldReason;
+
+		fn total_balance_on_hold(who: &T::AccountId) -> T::Balance {
+			execute_call_instance!(who)
+		}
+
+		fn reducible_total_balance_on_hold(who: &T::AccountId, force: Fortitude) -> T::Balance {
+			execute_call_instance!((who, force))
+		}
+
+		fn balance_on_hold(reason: &T::HoldReason, who: &T::AccountId) -> T::Balance {
+			execute_call_instance!((reason, who))
+		}
+
+		fn hold_available(reason: &T::HoldReason, who: &T::AccountId) -> bool {
+			execute_call_instance!((reason, who))
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> hold::Unbalanced<T::AccountId> for Pallet<T, I> {
+		fn set_balance_on_hold(
+			reason: &T::HoldReason,
+			who: &T::AccountId,
+			amount: T::Balance,
+		) -> DispatchResult {
+			execute_call_instance!((reason, who, amount))
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> hold::Mutate<T::AccountId> for Pallet<T, I> {}
+
+	impl<T: Config<I>, I: 'static> freeze::Inspect<T::AccountId> for Pallet<T, I> {
+		type Id = T::FreezeId;
+
+		fn balance_frozen(id: &T::FreezeId, who: &T::AccountId) -> T::Balance {
+			execute_call_instance!((id, who))
+		}
+
+		fn can_freeze(id: &T::FreezeId, who: &T::AccountId) -> bool {
+			execute_call_instance!((id, who))
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> freeze::Mutate<T::AccountId> for Pallet<T, I> {
+		fn set_freeze(id: &T::FreezeId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+			execute_call_instance!((id, who, amount))
+		}
+
+		fn extend_freeze(
+			id: &T::FreezeId,
+			who: &T::AccountId,
+			amount: T::Balance,
+		) -> DispatchResult {
+			execute_call_instance!((id, who, amount))
+		}
+
+		fn thaw(id: &T::FreezeId, who: &T::AccountId) -> DispatchResult {
+			execute_call_instance!((id, who))
+		}
+	}
+}
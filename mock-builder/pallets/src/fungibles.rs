@@ -0,0 +1,408 @@
+//! Mock pallet for the `fungibles` traits: `Inspect`, `Unbalanced`, `Mutate`
+//! and their `hold` and `freeze` counterparts.
+//!
+//! Each required method can be mocked through its `mock_<Trait>_<method>()`
+//! function, i.e. `mock_Inspect_balance()`. The provided methods, like
+//! `Mutate::transfer()` or `hold::Mutate::hold()`, are built on top of them.
+//!
+//! Calling [`Pallet::use_ledger()`](pallet::Pallet::use_ledger) registers all
+//! of them backed by a ledger in the pallet storage, obtaining realistic
+//! balances for any asset without configuring `pallet_assets`:
+//!
+//! ```ignore
+//! MockFungibles::use_ledger();
+//!
+//! assert_ok!(MockFungibles::mint_into(USDC, &ALICE, 100));
+//! assert_eq!(MockFungibles::balance(USDC, &ALICE), 100);
+//! ```
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet {
+	use alloc::string::String;
+
+	use frame_support::{
+		pallet_prelude::*,
+		traits::tokens::{
+			fungibles::{freeze, hold, Dust, Inspect, Mutate, Unbalanced},
+			DepositConsequence, Fortitude, Preservation, Provenance, WithdrawConsequence,
+		},
+	};
+	use mock_builder::{execute_call_instance, register_call_instance};
+	use sp_runtime::traits::Zero;
+
+	use crate::ledger::AccountData;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		type AssetId: frame_support::traits::tokens::AssetId;
+		type Balance: frame_support::traits::tokens::Balance;
+		type HoldReason: Parameter + 'static;
+		type FreezeId: Parameter + 'static;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(_);
+
+	#[pallet::storage]
+	type CallIds<T: Config<I>, I: 'static = ()> = StorageMap<_, _, String, mock_builder::CallId>;
+
+	#[pallet::storage]
+	type TotalIssuance<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, _, T::AssetId, T::Balance, ValueQuery>;
+
+	#[pallet::storage]
+	type Accounts<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		_,
+		T::AssetId,
+		_,
+		T::AccountId,
+		AccountData<T::Balance, T::HoldReason, T::FreezeId>,
+		ValueQuery,
+	>;
+
+	#[allow(non_snake_case)]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Register all methods backed by a ledger in the pallet storage, where
+		/// any asset exists and there is no existential deposit, as
+		/// [lax](mock_builder::strict) mocks.
+		pub fn use_ledger() {
+			mock_builder::strict::lax(|| {
+				Self::mock_Inspect_total_issuance(TotalIssuance::<T, I>::get);
+				Self::mock_Inspect_minimum_balance(|_| Zero::zero());
+				Self::mock_Inspect_total_balance(|asset, who| {
+					Accounts::<T, I>::get(asset, who).total()
+				});
+				Self::mock_Inspect_balance(|asset, who| Accounts::<T, I>::get(asset, who).free);
+				Self::mock_Inspect_reducible_balance(|asset, who, _, force| {
+					Accounts::<T, I>::get(asset, who).reducible(force)
+				});
+				Self::mock_Inspect_can_deposit(|asset, _, amount, _| {
+					match TotalIssuance::<T, I>::get(asset).checked_add(&amount) {
+						Some(_) => DepositConsequence::Success,
+						None => DepositConsequence::Overflow,
+					}
+				});
+				Self::mock_Inspect_can_withdraw(|asset, who, amount| {
+					Accounts::<T, I>::get(asset, who).can_withdraw(amount)
+				});
+				Self::mock_Inspect_asset_exists(|_| true);
+
+				Self::mock_Unbalanced_handle_dust(|_| ());
+				Self::mock_Unbalanced_write_balance(|asset, who, amount| {
+					Accounts::<T, I>::mutate(asset, who, |account| account.free = amount);
+					Ok(None)
+				});
+				Self::mock_Unbalanced_set_total_issuance(TotalIssuance::<T, I>::insert);
+
+				Self::mock_Inspect_total_balance_on_hold(|asset, who| {
+					Accounts::<T, I>::get(asset, who).total_on_hold()
+				});
+				Self::mock_Inspect_reducible_total_balance_on_hold(|asset, who, _| {
+					Accounts::<T, I>::get(asset, who).total_on_hold()
+				});
+				Self::mock_Inspect_balance_on_hold(|asset, reason, who| {
+					Accounts::<T, I>::get(asset, who).on_hold(reason)
+				});
+				Self::mock_Inspect_hold_available(|_, _, _| true);
+				Self::mock_Unbalanced_set_balance_on_hold(|asset, reason, who, amount| {
+					Accounts::<T, I>::mutate(asset, who, |account| {
+						account.set_on_hold(reason, amount)
+					});
+					Ok(())
+				});
+
+				Self::mock_Inspect_balance_frozen(|asset, id, who| {
+					Accounts::<T, I>::get(asset, who).frozen_by(id)
+				});
+				Self::mock_Inspect_can_freeze(|_, _, _| true);
+				Self::mock_Mutate_set_freeze(|asset, id, who, amount| {
+					Accounts::<T, I>::mutate(asset, who, |account| account.set_frozen(id, amount));
+					Ok(())
+				});
+				Self::mock_Mutate_extend_freeze(|asset, id, who, amount| {
+					Accounts::<T, I>::mutate(asset, who, |account| {
+						account.set_frozen(id, account.frozen_by(id).max(amount))
+					});
+					Ok(())
+				});
+				Self::mock_Mutate_thaw(|asset, id, who| {
+					Accounts::<T, I>::mutate(asset, who, |account| {
+						account.set_frozen(id, Zero::zero())
+					});
+					Ok(())
+				});
+			})
+		}
+
+		pub fn mock_Inspect_total_issuance(f: impl Fn(T::AssetId) -> T::Balance + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_Inspect_minimum_balance(f: impl Fn(T::AssetId) -> T::Balance + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_Inspect_total_balance(
+			f: impl Fn(T::AssetId, &T::AccountId) -> T::Balance + 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_Inspect_balance(f: impl Fn(T::AssetId, &T::AccountId) -> T::Balance + 'static) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_Inspect_reducible_balance(
+			f: impl Fn(T::AssetId, &T::AccountId, Preservation, Fortitude) -> T::Balance + 'static,
+		) {
+			register_call_instance!(move |(a, b, c, d)| f(a, b, c, d));
+		}
+
+		pub fn mock_Inspect_can_deposit(
+			f: impl Fn(T::AssetId, &T::AccountId, T::Balance, Provenance) -> DepositConsequence
+				+ 'static,
+		) {
+			register_call_instance!(move |(a, b, c, d)| f(a, b, c, d));
+		}
+
+		pub fn mock_Inspect_can_withdraw(
+			f: impl Fn(T::AssetId, &T::AccountId, T::Balance) -> WithdrawConsequence<T::Balance>
+				+ 'static,
+		) {
+			register_call_instance!(move |(a, b, c)| f(a, b, c));
+		}
+
+		pub fn mock_Inspect_asset_exists(f: impl Fn(T::AssetId) -> bool + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_Unbalanced_handle_dust(f: impl Fn(Dust<T::AccountId, Self>) + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_Unbalanced_write_balance(
+			f: impl Fn(
+					T::AssetId,
+					&T::AccountId,
+					T::Balance,
+				) -> Result<Option<T::Balance>, DispatchError>
+				+ 'static,
+		) {
+			register_call_instance!(move |(a, b, c)| f(a, b, c));
+		}
+
+		pub fn mock_Unbalanced_set_total_issuance(f: impl Fn(T::AssetId, T::Balance) + 'static) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_Inspect_total_balance_on_hold(
+			f: impl Fn(T::AssetId, &T::AccountId) -> T::Balance + 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_Inspect_reducible_total_balance_on_hold(
+			f: impl Fn(T::AssetId, &T::AccountId, Fortitude) -> T::Balance + 'static,
+		) {
+			register_call_instance!(move |(a, b, c)| f(a, b, c));
+		}
+
+		pub fn mock_Inspect_balance_on_hold(
+			f: impl Fn(T::AssetId, &T::HoldReason, &T::AccountId) -> T::Balance + 'static,
+		) {
+			register_call_instance!(move |(a, b, c)| f(a, b, c));
+		}
+
+		pub fn mock_Inspect_hold_available(
+			f: impl Fn(T::AssetId, &T::HoldReason, &T::AccountId) -> bool + 'static,
+		) {
+			register_call_instance!(move |(a, b, c)| f(a, b, c));
+		}
+
+		pub fn mock_Unbalanced_set_balance_on_hold(
+			f: impl Fn(T::AssetId, &T::HoldReason, &T::AccountId, T::Balance) -> DispatchResult
+				+ 'static,
+		) {
+			register_call_instance!(move |(a, b, c, d)| f(a, b, c, d));
+		}
+
+		pub fn mock_Inspect_balance_frozen(
+			f: impl Fn(T::AssetId, &T::FreezeId, &T::AccountId) -> T::Balance + 'static,
+		) {
+			register_call_instance!(move |(a, b, c)| f(a, b, c));
+		}
+
+		pub fn mock_Inspect_can_freeze(
+			f: impl Fn(T::AssetId, &T::FreezeId, &T::AccountId) -> bool + 'static,
+		) {
+			register_call_instance!(move |(a, b, c)| f(a, b, c));
+		}
+
+		pub fn mock_Mutate_set_freeze(
+			f: impl Fn(T::AssetId, &T::FreezeId, &T::AccountId, T::Balance) -> DispatchResult + 'static,
+		) {
+			register_call_instance!(move |(a, b, c, d)| f(a, b, c, d));
+		}
+
+		pub fn mock_Mutate_extend_freeze(
+			f: impl Fn(T::AssetId, &T::FreezeId, &T::AccountId, T::Balance) -> DispatchResult + 'static,
+		) {
+			register_call_instance!(move |(a, b, c, d)| f(a, b, c, d));
+		}
+
+		pub fn mock_Mutate_thaw(
+			f: impl Fn(T::AssetId, &T::FreezeId, &T::AccountId) -> DispatchResult + 'static,
+		) {
+			register_call_instance!(move |(a, b, c)| f(a, b, c));
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Inspect<T::AccountId> for Pallet<T, I> {
+		type AssetId = T::AssetId;
+		type Balance = T::Balance;
+
+		fn total_issuance(asset: T::AssetId) -> T::Balance {
+			execute_call_instance!(asset)
+		}
+
+		fn minimum_balance(asset: T::AssetId) -> T::Balance {
+			execute_call_instance!(asset)
+		}
+
+		fn total_balance(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+			execute_call_instance!((asset, who))
+		}
+
+		fn balance(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+			execute_call_instance!((asset, who))
+		}
+
+		fn reducible_balance(
+			asset: T::AssetId,
+			who: &T::AccountId,
+			preservation: Preservation,
+			force: Fortitude,
+		) -> T::Balance {
+			execute_call_instance!((asset, who, preservation, force))
+		}
+
+		fn can_deposit(
+			asset: T::AssetId,
+			who: &T::AccountId,
+			amount: T::Balance,
+			provenance: Provenance,
+		) -> DepositConsequence {
+			execute_call_instance!((asset, who, amount, provenance))
+		}
+
+		fn can_withdraw(
+			asset: T::AssetId,
+			who: &T::AccountId,
+			amount: T::Balance,
+		) -> WithdrawConsequence<T::Balance> {
+			execute_call_instance!((asset, who, amount))
+		}
+
+		fn asset_exists(asset: T::AssetId) -> bool {
+			execute_call_instance!(asset)
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Unbalanced<T::AccountId> for Pallet<T, I> {
+		fn handle_dust(dust: Dust<T::AccountId, Self>) {
+			execute_call_instance!(dust)
+		}
+
+		fn write_balance(
+			asset: T::AssetId,
+			who: &T::AccountId,
+			amount: T::Balance,
+		) -> Result<Option<T::Balance>, DispatchError> {
+			execute_call_instance!((asset, who, amount))
+		}
+
+		fn set_total_issuance(asset: T::AssetId, amount: T::Balance) {
+			execute_call_instance!((asset, amount))
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Mutate<T::AccountId> for Pallet<T, I> {}
+
+	impl<T: Config<I>, I: 'static> hold::Inspect<T::AccountId> for Pallet<T, I> {
+		type Reason = T::HoldReason;
+
+		fn total_balance_on_hold(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+			execute_call_instance!((asset, who))
+		}
+
+		fn reducible_total_balance_on_hold(
+			asset: T::AssetId,
+			who: &T::AccountId,
+			force: Fortitude,
+		) -> T::Balance {
+			execute_call_instance!((asset, who, force))
+		}
+
+		fn balance_on_hold(
+			asset: T::AssetId,
+			reason: &T::HoldReason,
+			who: &T::AccountId,
+		) -> T::Balance {
+			execute_call_instance!((asset, reason, who))
+		}
+
+		fn hold_available(asset: T::AssetId, reason: &T::HoldReason, who: &T::AccountId) -> bool {
+			execute_call_instance!((asset, reason, who))
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> hold::Unbalanced<T::AccountId> for Pallet<T, I> {
+		fn set_balance_on_hold(
+			asset: T::AssetId,
+			reason: &T::HoldReason,
+			who: &T::AccountId,
+			amount: T::Balance,
+		) -> DispatchResult {
+			execute_call_instance!((asset, reason, who, amount))
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> hold::Mutate<T::AccountId> for Pallet<T, I> {}
+
+	impl<T: Config<I>, I: 'static> freeze::Inspect<T::AccountId> for Pallet<T, I> {
+		type Id = T::FreezeId;
+
+		fn balance_frozen(asset: T::AssetId, id: &T::FreezeId, who: &T::AccountId) -> T::Balance {
+			execute_call_instance!((asset, id, who))
+		}
+
+		fn can_freeze(asset: T::AssetId, id: &T::FreezeId, who: &T::AccountId) -> bool {
+			execute_call_instance!((asset, id, who))
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> freeze::Mutate<T::AccountId> for Pallet<T, I> {
+		fn set_freeze(
+			asset: T::AssetId,
+			id: &T::FreezeId,
+			who: &T::AccountId,
+			amount: T::Balance,
+		) -> DispatchResult {
+			execute_call_instance!((asset, id, who, amount))
+		}
+
+		fn extend_freeze(
+			asset: T::AssetId,
+			id: &T::FreezeId,
+			who: &T::AccountId,
+			amount: T::Balance,
+		) -> DispatchResult {
+			execute_call_instance!((asset, id, who, amount))
+		}
+
+		fn thaw(asset: T::AssetId, id: &T::FreezeId, who: &T::AccountId) -> DispatchResult {
+			execute_call_instance!((asset, id, who))
+		}
+	}
+}
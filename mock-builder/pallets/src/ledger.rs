@@ -0,0 +1,102 @@
+//! Account balances used by the in-memory ledger of the fungible mock pallets.
+//! Freezes overlap between them and also apply to the balance on hold, as in
+//! `pallet_balances`. There is no existential deposit.
+
+use alloc::vec::Vec;
+
+use frame_support::traits::tokens::{Balance, Fortitude, WithdrawConsequence};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Zero, RuntimeDebug};
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AccountData<B, Reason, Id> {
+	pub free: B,
+	pub holds: Vec<(Reason, B)>,
+	pub freezes: Vec<(Id, B)>,
+}
+
+impl<B: Balance, Reason, Id> Default for AccountData<B, Reason, Id> {
+	fn default() -> Self {
+		Self {
+			free: Zero::zero(),
+			holds: Vec::new(),
+			freezes: Vec::new(),
+		}
+	}
+}
+
+impl<B: Balance, Reason: PartialEq, Id: PartialEq> AccountData<B, Reason, Id> {
+	pub fn total(&self) -> B {
+		self.free.saturating_add(self.total_on_hold())
+	}
+
+	pub fn total_on_hold(&self) -> B {
+		self.holds.iter().fold(Zero::zero(), |total, (_, amount)| {
+			total.saturating_add(*amount)
+		})
+	}
+
+	pub fn on_hold(&self, reason: &Reason) -> B {
+		find(&self.holds, reason)
+	}
+
+	pub fn set_on_hold(&mut self, reason: &Reason, amount: B)
+	where
+		Reason: Clone,
+	{
+		set(&mut self.holds, reason, amount)
+	}
+
+	/// The largest freeze, since all of them overlap.
+	pub fn frozen(&self) -> B {
+		self.freezes
+			.iter()
+			.fold(Zero::zero(), |frozen, (_, amount)| frozen.max(*amount))
+	}
+
+	pub fn frozen_by(&self, id: &Id) -> B {
+		find(&self.freezes, id)
+	}
+
+	pub fn set_frozen(&mut self, id: &Id, amount: B)
+	where
+		Id: Clone,
+	{
+		set(&mut self.freezes, id, amount)
+	}
+
+	pub fn reducible(&self, force: Fortitude) -> B {
+		match force {
+			Fortitude::Polite => self
+				.free
+				.saturating_sub(self.frozen().saturating_sub(self.total_on_hold())),
+			Fortitude::Force => self.free,
+		}
+	}
+
+	pub fn can_withdraw(&self, amount: B) -> WithdrawConsequence<B> {
+		if amount > self.free {
+			WithdrawConsequence::BalanceLow
+		} else if amount > self.reducible(Fortitude::Polite) {
+			WithdrawConsequence::Frozen
+		} else {
+			WithdrawConsequence::Success
+		}
+	}
+}
+
+fn find<K: PartialEq, B: Balance>(entries: &[(K, B)], key: &K) -> B {
+	entries
+		.iter()
+		.find(|(k, _)| k == key)
+		.map(|(_, amount)| *amount)
+		.unwrap_or_else(Zero::zero)
+}
+
+fn set<K: PartialEq + Clone, B: Balance>(entries: &mut Vec<(K, B)>, key: &K, amount: B) {
+	entries.retain(|(k, _)| k != key);
+	if !amount.is_zero() {
+		entries.push((key.clone(), amount));
+	}
+}
@@ -0,0 +1,56 @@
+// Copyright 2023 Centrifuge Foundation (centrifuge.io).
+// This file is part of Centrifuge chain project.
+
+// Centrifuge is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version (see http://www.gnu.org/licenses).
+
+// Centrifuge is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+//! Ready-made *mock pallets*, built with `mock-builder`, for the FRAME traits
+//! most pallets depend on. They're added to a runtime as any other *mock
+//! pallet*, and they support instances:
+//!
+//! ```ignore
+//! frame_support::construct_runtime!(
+//!     pub struct Runtime {
+//!         System: frame_system,
+//!         MockFungible: pallet_mock_fungible,
+//!         MyPallet: my_pallet,
+//!     }
+//! );
+//!
+//! impl pallet_mock_fungible::Config for Runtime {
+//!     type Balance = u64;
+//!     type HoldReason = HoldReason;
+//!     type FreezeId = FreezeId;
+//! }
+//!
+//! impl my_pallet::Config for Runtime {
+//!     type Currency = MockFungible;
+//! }
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod ledger;
+
+pub mod filter;
+pub mod fungible;
+pub mod fungibles;
+pub mod origin;
+pub mod scheduler;
+pub mod time;
+
+pub use filter::{pallet as pallet_mock_filter, MockInstanceFilter};
+pub use fungible::pallet as pallet_mock_fungible;
+pub use fungibles::pallet as pallet_mock_fungibles;
+pub use origin::{pallet as pallet_mock_origin, MockOrigin};
+pub use scheduler::pallet as pallet_mock_scheduler;
+pub use time::pallet as pallet_mock_time;
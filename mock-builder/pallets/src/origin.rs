@@ -0,0 +1,101 @@
+//! Mock pallet for the `EnsureOrigin` and `EnsureOriginWithArg` traits, to be
+//! used as the origin type of the pallet under test:
+//!
+//! ```ignore
+//! impl my_pallet::Config for Runtime {
+//!     type AdminOrigin = MockOrigin<Runtime>;
+//! }
+//! ```
+//!
+//! The pallet must be added to the runtime as any other *mock pallet*. Use an
+//! instance for each origin type mocked. Both traits have a `try_origin()`
+//! method, so the trait is always part of the `mock_*` function name:
+//!
+//! ```ignore
+//! MockOrigin::mock_EnsureOrigin_try_origin(|origin| {
+//!     ensure_root(origin.clone()).map_err(|_| origin)
+//! });
+//!
+//! assert_noop!(MyPallet::admin_call(RuntimeOrigin::signed(1)), BadOrigin);
+//! ```
+//!
+//! `try_successful_origin()` is only part of the traits with the
+//! `runtime-benchmarks` feature.
+
+/// The mock pallet used as an origin type.
+pub type MockOrigin<T, I = ()> = pallet::Pallet<T, I>;
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet {
+	use alloc::string::String;
+
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{EnsureOrigin, EnsureOriginWithArg},
+	};
+	use mock_builder::{execute_call_instance, register_call_instance};
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		type Success;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(_);
+
+	#[pallet::storage]
+	type CallIds<T: Config<I>, I: 'static = ()> = StorageMap<_, _, String, mock_builder::CallId>;
+
+	#[allow(non_snake_case)]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		pub fn mock_EnsureOrigin_try_origin(
+			f: impl Fn(T::RuntimeOrigin) -> Result<T::Success, T::RuntimeOrigin> + 'static,
+		) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_EnsureOrigin_try_successful_origin(
+			f: impl Fn() -> Result<T::RuntimeOrigin, ()> + 'static,
+		) {
+			register_call_instance!(move |()| f());
+		}
+
+		pub fn mock_EnsureOriginWithArg_try_origin<A: 'static>(
+			f: impl Fn(T::RuntimeOrigin, &A) -> Result<T::Success, T::RuntimeOrigin> + 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_EnsureOriginWithArg_try_successful_origin<A: 'static>(
+			f: impl Fn(&A) -> Result<T::RuntimeOrigin, ()> + 'static,
+		) {
+			register_call_instance!(f);
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> EnsureOrigin<T::RuntimeOrigin> for Pallet<T, I> {
+		type Success = T::Success;
+
+		fn try_origin(o: T::RuntimeOrigin) -> Result<T::Success, T::RuntimeOrigin> {
+			execute_call_instance!(o)
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn try_successful_origin() -> Result<T::RuntimeOrigin, ()> {
+			execute_call_instance!(())
+		}
+	}
+
+	impl<T: Config<I>, I: 'static, A> EnsureOriginWithArg<T::RuntimeOrigin, A> for Pallet<T, I> {
+		type Success = T::Success;
+
+		fn try_origin(o: T::RuntimeOrigin, a: &A) -> Result<T::Success, T::RuntimeOrigin> {
+			execute_call_instance!((o, a))
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn try_successful_origin(a: &A) -> Result<T::RuntimeOrigin, ()> {
+			execute_call_instance!(a)
+		}
+	}
+}
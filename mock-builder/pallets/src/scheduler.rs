@@ -0,0 +1,419 @@
+//! Mock pallet for the scheduler traits, `schedule::v3::{Anon, Named}`, and
+//! the preimage traits, `QueryPreimage` and `StorePreimage`.
+//!
+//! Calling [`Pallet::use_agenda()`](pallet::Pallet::use_agenda) registers the
+//! scheduler methods recording the tasks in the pallet storage, and
+//! [`Pallet::use_preimages()`](pallet::Pallet::use_preimages) registers the
+//! preimage methods backed by the pallet storage. A recorded task can be
+//! inspected with [`Pallet::task()`](pallet::Pallet::task) and dispatched with
+//! [`Pallet::fire()`](pallet::Pallet::fire), without running any block:
+//!
+//! ```ignore
+//! MockScheduler::use_agenda();
+//! MockScheduler::use_preimages();
+//!
+//! assert_ok!(MyPallet::propose(RuntimeOrigin::signed(ALICE), call));
+//!
+//! let (id, task) = MockScheduler::tasks().pop().unwrap();
+//! assert_eq!(task.when, 10);
+//! assert_ok!(MockScheduler::fire(id));
+//! ```
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet {
+	use alloc::{borrow::Cow, string::String, vec::Vec};
+
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			schedule::{
+				v3::{Anon, Named, TaskName},
+				DispatchTime, Period, Priority,
+			},
+			Bounded, FetchResult, QueryPreimage, StorePreimage,
+		},
+		CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+	};
+	use frame_system::pallet_prelude::BlockNumberFor;
+	use mock_builder::{execute_call_instance, register_call_instance};
+	use sp_runtime::traits::{Dispatchable, Hash, One, Saturating};
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		type PalletsOrigin: Parameter + Into<<Self as frame_system::Config>::RuntimeOrigin>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(_);
+
+	/// A task recorded by the scheduler methods registered with
+	/// [`Pallet::use_agenda()`].
+	#[derive(
+		Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo,
+	)]
+	#[scale_info(skip_type_params(T, I))]
+	pub struct Task<T: Config<I>, I: 'static = ()> {
+		pub name: Option<TaskName>,
+		pub when: BlockNumberFor<T>,
+		pub maybe_periodic: Option<Period<BlockNumberFor<T>>>,
+		pub priority: Priority,
+		pub origin: T::PalletsOrigin,
+		pub call: Bounded<T::RuntimeCall, T::Hashing>,
+	}
+
+	#[pallet::storage]
+	type CallIds<T: Config<I>, I: 'static = ()> = StorageMap<_, _, String, mock_builder::CallId>;
+
+	#[pallet::storage]
+	type NextTaskId<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	type Tasks<T: Config<I>, I: 'static = ()> = StorageMap<_, _, u32, Task<T, I>>;
+
+	#[pallet::storage]
+	type Names<T: Config<I>, I: 'static = ()> = StorageMap<_, _, TaskName, u32>;
+
+	#[pallet::storage]
+	type Preimages<T: Config<I>, I: 'static = ()> = StorageMap<_, _, T::Hash, Vec<u8>>;
+
+	#[pallet::storage]
+	type Requests<T: Config<I>, I: 'static = ()> = StorageMap<_, _, T::Hash, u32, ValueQuery>;
+
+	#[allow(non_snake_case)]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Register all scheduler methods recording the tasks in the pallet
+		/// storage, where the address of a task is its `u32` identifier, as
+		/// [lax](mock_builder::strict) mocks.
+		pub fn use_agenda() {
+			mock_builder::strict::lax(|| {
+				Self::mock_Anon_schedule(|when, maybe_periodic, priority, origin, call| {
+					Self::add_task(None, when, maybe_periodic, priority, origin, call)
+				});
+				Self::mock_Anon_cancel(|id| Self::remove_task(id).map(|_| ()));
+				Self::mock_Anon_reschedule(Self::reschedule_task);
+				Self::mock_Anon_next_dispatch_time(|id| Ok(Self::task(id)?.when));
+
+				Self::mock_Named_schedule_named(
+					|name, when, maybe_periodic, priority, origin, call| {
+						if Names::<T, I>::contains_key(name) {
+							return Err(DispatchError::Other("Task name already scheduled"));
+						}
+						Self::add_task(Some(name), when, maybe_periodic, priority, origin, call)
+					},
+				);
+				Self::mock_Named_cancel_named(|name| {
+					Self::remove_task(Self::task_id(name)?).map(|_| ())
+				});
+				Self::mock_Named_reschedule_named(|name, when| {
+					Self::reschedule_task(Self::task_id(name)?, when)
+				});
+				Self::mock_Named_next_dispatch_time(|name| {
+					Ok(Self::task(Self::task_id(name)?)?.when)
+				});
+			})
+		}
+
+		/// Register all preimage methods backed by the pallet storage, as
+		/// [lax](mock_builder::strict) mocks.
+		pub fn use_preimages() {
+			mock_builder::strict::lax(|| {
+				Self::mock_QueryPreimage_len(|hash| {
+					Preimages::<T, I>::get(hash).map(|bytes| bytes.len() as u32)
+				});
+				Self::mock_QueryPreimage_fetch(|hash, _| {
+					Preimages::<T, I>::get(hash)
+						.map(Cow::from)
+						.ok_or(DispatchError::Unavailable)
+				});
+				Self::mock_QueryPreimage_is_requested(|hash| Requests::<T, I>::get(hash) > 0);
+				Self::mock_QueryPreimage_request(|hash| {
+					Requests::<T, I>::mutate(hash, |count| *count += 1)
+				});
+				Self::mock_QueryPreimage_unrequest(|hash| {
+					Requests::<T, I>::mutate(hash, |count| *count = count.saturating_sub(1))
+				});
+				Self::mock_StorePreimage_note(|bytes| {
+					let hash = T::Hashing::hash(&bytes);
+					Preimages::<T, I>::insert(hash, bytes.into_owned());
+					Ok(hash)
+				});
+			})
+		}
+
+		/// The tasks recorded by [`Pallet::use_agenda()`], sorted by their
+		/// identifier.
+		pub fn tasks() -> Vec<(u32, Task<T, I>)> {
+			let mut tasks = Tasks::<T, I>::iter().collect::<Vec<_>>();
+			tasks.sort_by_key(|(id, _)| *id);
+			tasks
+		}
+
+		pub fn task(id: u32) -> Result<Task<T, I>, DispatchError> {
+			Tasks::<T, I>::get(id).ok_or(DispatchError::Unavailable)
+		}
+
+		pub fn task_id(name: TaskName) -> Result<u32, DispatchError> {
+			Names::<T, I>::get(name).ok_or(DispatchError::Unavailable)
+		}
+
+		/// Remove a recorded task and dispatch its call with its origin, as the
+		/// scheduler would do at the scheduled block. Periodic tasks are not
+		/// scheduled again. A call stored as a preimage is obtained through
+		/// `QueryPreimage`, so it also needs [`Pallet::use_preimages()`].
+		pub fn fire(id: u32) -> DispatchResult {
+			let task = Self::remove_task(id)?;
+			let (call, _) = Self::realize(&task.call)?;
+
+			call.dispatch(task.origin.into())
+				.map(|_| ())
+				.map_err(|e| e.error)
+		}
+
+		fn add_task(
+			name: Option<TaskName>,
+			when: DispatchTime<BlockNumberFor<T>>,
+			maybe_periodic: Option<Period<BlockNumberFor<T>>>,
+			priority: Priority,
+			origin: T::PalletsOrigin,
+			call: Bounded<T::RuntimeCall, T::Hashing>,
+		) -> Result<u32, DispatchError> {
+			let id = NextTaskId::<T, I>::mutate(|next| {
+				let id = *next;
+				*next += 1;
+				id
+			});
+
+			if let Some(name) = name {
+				Names::<T, I>::insert(name, id);
+			}
+
+			let task = Task {
+				name,
+				when: Self::resolve_time(when),
+				maybe_periodic,
+				priority,
+				origin,
+				call,
+			};
+			Tasks::<T, I>::insert(id, task);
+
+			Ok(id)
+		}
+
+		fn remove_task(id: u32) -> Result<Task<T, I>, DispatchError> {
+			let task = Tasks::<T, I>::take(id).ok_or(DispatchError::Unavailable)?;
+			if let Some(name) = task.name {
+				Names::<T, I>::remove(name);
+			}
+
+			Ok(task)
+		}
+
+		fn reschedule_task(
+			id: u32,
+			when: DispatchTime<BlockNumberFor<T>>,
+		) -> Result<u32, DispatchError> {
+			Tasks::<T, I>::try_mutate(id, |task| match task {
+				Some(task) => {
+					task.when = Self::resolve_time(when);
+					Ok(id)
+				}
+				None => Err(DispatchError::Unavailable),
+			})
+		}
+
+		fn resolve_time(when: DispatchTime<BlockNumberFor<T>>) -> BlockNumberFor<T> {
+			match when {
+				DispatchTime::At(block) => block,
+				DispatchTime::After(blocks) => frame_system::Pallet::<T>::block_number()
+					.saturating_add(blocks)
+					.saturating_add(One::one()),
+			}
+		}
+
+		pub fn mock_Anon_schedule(
+			f: impl Fn(
+					DispatchTime<BlockNumberFor<T>>,
+					Option<Period<BlockNumberFor<T>>>,
+					Priority,
+					T::PalletsOrigin,
+					Bounded<T::RuntimeCall, T::Hashing>,
+				) -> Result<u32, DispatchError>
+				+ 'static,
+		) {
+			register_call_instance!(move |(a, b, c, d, e)| f(a, b, c, d, e));
+		}
+
+		pub fn mock_Anon_cancel(f: impl Fn(u32) -> DispatchResult + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_Anon_reschedule(
+			f: impl Fn(u32, DispatchTime<BlockNumberFor<T>>) -> Result<u32, DispatchError> + 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_Anon_next_dispatch_time(
+			f: impl Fn(u32) -> Result<BlockNumberFor<T>, DispatchError> + 'static,
+		) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_Named_schedule_named(
+			f: impl Fn(
+					TaskName,
+					DispatchTime<BlockNumberFor<T>>,
+					Option<Period<BlockNumberFor<T>>>,
+					Priority,
+					T::PalletsOrigin,
+					Bounded<T::RuntimeCall, T::Hashing>,
+				) -> Result<u32, DispatchError>
+				+ 'static,
+		) {
+			register_call_instance!(move |(a, b, c, d, e, g)| f(a, b, c, d, e, g));
+		}
+
+		pub fn mock_Named_cancel_named(f: impl Fn(TaskName) -> DispatchResult + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_Named_reschedule_named(
+			f: impl Fn(TaskName, DispatchTime<BlockNumberFor<T>>) -> Result<u32, DispatchError>
+				+ 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_Named_next_dispatch_time(
+			f: impl Fn(TaskName) -> Result<BlockNumberFor<T>, DispatchError> + 'static,
+		) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_QueryPreimage_len(f: impl Fn(&T::Hash) -> Option<u32> + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_QueryPreimage_fetch(
+			f: impl Fn(&T::Hash, Option<u32>) -> FetchResult + 'static,
+		) {
+			register_call_instance!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_QueryPreimage_is_requested(f: impl Fn(&T::Hash) -> bool + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_QueryPreimage_request(f: impl Fn(&T::Hash) + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_QueryPreimage_unrequest(f: impl Fn(&T::Hash) + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_StorePreimage_note(
+			f: impl Fn(Cow<[u8]>) -> Result<T::Hash, DispatchError> + 'static,
+		) {
+			register_call_instance!(f);
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Anon<BlockNumberFor<T>, T::RuntimeCall, T::PalletsOrigin>
+		for Pallet<T, I>
+	{
+		type Address = u32;
+		type Hasher = T::Hashing;
+
+		fn schedule(
+			when: DispatchTime<BlockNumberFor<T>>,
+			maybe_periodic: Option<Period<BlockNumberFor<T>>>,
+			priority: Priority,
+			origin: T::PalletsOrigin,
+			call: Bounded<T::RuntimeCall, T::Hashing>,
+		) -> Result<u32, DispatchError> {
+			execute_call_instance!((when, maybe_periodic, priority, origin, call))
+		}
+
+		fn cancel(address: u32) -> DispatchResult {
+			execute_call_instance!(address)
+		}
+
+		fn reschedule(
+			address: u32,
+			when: DispatchTime<BlockNumberFor<T>>,
+		) -> Result<u32, DispatchError> {
+			execute_call_instance!((address, when))
+		}
+
+		fn next_dispatch_time(address: u32) -> Result<BlockNumberFor<T>, DispatchError> {
+			execute_call_instance!(address)
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Named<BlockNumberFor<T>, T::RuntimeCall, T::PalletsOrigin>
+		for Pallet<T, I>
+	{
+		type Address = u32;
+		type Hasher = T::Hashing;
+
+		fn schedule_named(
+			id: TaskName,
+			when: DispatchTime<BlockNumberFor<T>>,
+			maybe_periodic: Option<Period<BlockNumberFor<T>>>,
+			priority: Priority,
+			origin: T::PalletsOrigin,
+			call: Bounded<T::RuntimeCall, T::Hashing>,
+		) -> Result<u32, DispatchError> {
+			execute_call_instance!((id, when, maybe_periodic, priority, origin, call))
+		}
+
+		fn cancel_named(id: TaskName) -> DispatchResult {
+			execute_call_instance!(id)
+		}
+
+		fn reschedule_named(
+			id: TaskName,
+			when: DispatchTime<BlockNumberFor<T>>,
+		) -> Result<u32, DispatchError> {
+			execute_call_instance!((id, when))
+		}
+
+		fn next_dispatch_time(id: TaskName) -> Result<BlockNumberFor<T>, DispatchError> {
+			execute_call_instance!(id)
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> QueryPreimage for Pallet<T, I> {
+		type H = T::Hashing;
+
+		fn len(hash: &T::Hash) -> Option<u32> {
+			execute_call_instance!(hash)
+		}
+
+		fn fetch(hash: &T::Hash, len: Option<u32>) -> FetchResult {
+			execute_call_instance!((hash, len))
+		}
+
+		fn is_requested(hash: &T::Hash) -> bool {
+			execute_call_instance!(hash)
+		}
+
+		fn request(hash: &T::Hash) {
+			execute_call_instance!(hash)
+		}
+
+		fn unrequest(hash: &T::Hash) {
+			execute_call_instance!(hash)
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> StorePreimage for Pallet<T, I> {
+		const MAX_LENGTH: usize = 4 * 1024 * 1024;
+
+		fn note(bytes: Cow<[u8]>) -> Result<T::Hash, DispatchError> {
+			execute_call_instance!(bytes)
+		}
+	}
+}
@@ -0,0 +1,134 @@
+//! Mock pallet for the time, randomness and session related traits:
+//! `UnixTime`, `Time`, `Randomness` and `EstimateNextSessionRotation`.
+//!
+//! Both time traits have a `now()` method, so the trait is always part of the
+//! `mock_*` function name. [`Pallet::set_now()`](pallet::Pallet::set_now)
+//! stores an instant, given in milliseconds, returned by both of them instead
+//! of their mocks until [`Pallet::clear_now()`](pallet::Pallet::clear_now) is
+//! called, which allows to test time based code without `pallet_timestamp`:
+//!
+//! ```ignore
+//! MockTime::set_now(1_000);
+//! assert_ok!(MyPallet::start_auction(RuntimeOrigin::signed(ALICE)));
+//!
+//! MockTime::set_now(1_000 + AUCTION_DURATION);
+//! assert_ok!(MyPallet::close_auction(RuntimeOrigin::signed(ALICE)));
+//! ```
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet {
+	use alloc::string::String;
+	use core::time::Duration;
+
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{EstimateNextSessionRotation, Randomness, Time, UnixTime},
+	};
+	use frame_system::pallet_prelude::BlockNumberFor;
+	use mock_builder::{execute_call_instance, register_call_instance};
+	use sp_runtime::{
+		traits::{AtLeast32Bit, SaturatedConversion},
+		Permill,
+	};
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		type Moment: AtLeast32Bit + Parameter + Default + Copy + MaxEncodedLen;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(_);
+
+	#[pallet::storage]
+	type CallIds<T: Config<I>, I: 'static = ()> = StorageMap<_, _, String, mock_builder::CallId>;
+
+	#[pallet::storage]
+	type Now<T: Config<I>, I: 'static = ()> = StorageValue<_, u64>;
+
+	#[allow(non_snake_case)]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Set the instant, in milliseconds, returned by `UnixTime::now()` and
+		/// `Time::now()` from now on, instead of their mocks.
+		pub fn set_now(ms: u64) {
+			Now::<T, I>::put(ms);
+		}
+
+		/// Remove the instant set by [`Pallet::set_now()`], so `UnixTime::now()`
+		/// and `Time::now()` use their mocks again.
+		pub fn clear_now() {
+			Now::<T, I>::kill();
+		}
+
+		pub fn mock_UnixTime_now(f: impl Fn() -> Duration + 'static) {
+			register_call_instance!(move |()| f());
+		}
+
+		pub fn mock_Time_now(f: impl Fn() -> T::Moment + 'static) {
+			register_call_instance!(move |()| f());
+		}
+
+		pub fn mock_Randomness_random(f: impl Fn(&[u8]) -> (T::Hash, BlockNumberFor<T>) + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_EstimateNextSessionRotation_average_session_length(
+			f: impl Fn() -> BlockNumberFor<T> + 'static,
+		) {
+			register_call_instance!(move |()| f());
+		}
+
+		pub fn mock_EstimateNextSessionRotation_estimate_current_session_progress(
+			f: impl Fn(BlockNumberFor<T>) -> (Option<Permill>, Weight) + 'static,
+		) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_EstimateNextSessionRotation_estimate_next_session_rotation(
+			f: impl Fn(BlockNumberFor<T>) -> (Option<BlockNumberFor<T>>, Weight) + 'static,
+		) {
+			register_call_instance!(f);
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> UnixTime for Pallet<T, I> {
+		fn now() -> Duration {
+			match Now::<T, I>::get() {
+				Some(ms) => Duration::from_millis(ms),
+				None => execute_call_instance!(()),
+			}
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Time for Pallet<T, I> {
+		type Moment = T::Moment;
+
+		fn now() -> T::Moment {
+			match Now::<T, I>::get() {
+				Some(ms) => ms.saturated_into(),
+				None => execute_call_instance!(()),
+			}
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Randomness<T::Hash, BlockNumberFor<T>> for Pallet<T, I> {
+		fn random(subject: &[u8]) -> (T::Hash, BlockNumberFor<T>) {
+			execute_call_instance!(subject)
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> EstimateNextSessionRotation<BlockNumberFor<T>> for Pallet<T, I> {
+		fn average_session_length() -> BlockNumberFor<T> {
+			execute_call_instance!(())
+		}
+
+		fn estimate_current_session_progress(now: BlockNumberFor<T>) -> (Option<Permill>, Weight) {
+			execute_call_instance!(now)
+		}
+
+		fn estimate_next_session_rotation(
+			now: BlockNumberFor<T>,
+		) -> (Option<BlockNumberFor<T>>, Weight) {
+			execute_call_instance!(now)
+		}
+	}
+}
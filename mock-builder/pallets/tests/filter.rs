@@ -0,0 +1,72 @@
+use frame_support::{
+	assert_noop, assert_ok, derive_impl,
+	traits::{Contains, Get, InstanceFilter},
+};
+use frame_system::Call as SystemCall;
+use mock_builder_pallets::{pallet_mock_filter, MockInstanceFilter};
+use sp_runtime::{traits::Dispatchable, DispatchError};
+
+frame_support::construct_runtime!(
+	pub struct Runtime {
+		System: frame_system,
+		MockFilter: pallet_mock_filter,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = MockFilter;
+	type Block = frame_system::mocking::MockBlock<Runtime>;
+}
+
+impl pallet_mock_filter::Config for Runtime {}
+
+type ProxyType = MockInstanceFilter<Runtime>;
+
+const ANY: u8 = 0;
+const NON_REMARK: u8 = 1;
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(SystemCall::remark { remark: vec![] })
+}
+
+fn set_heap_pages() -> RuntimeCall {
+	RuntimeCall::System(SystemCall::set_heap_pages { pages: 1 })
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn base_call_filter() {
+	MockFilter::mock_contains(|call: &RuntimeCall| call != &remark());
+
+	assert!(!MockFilter::contains(&remark()));
+	assert_noop!(
+		remark().dispatch(RuntimeOrigin::signed(1)),
+		DispatchError::from(frame_system::Error::<Runtime>::CallFiltered)
+	);
+
+	MockFilter::mock_contains(|_: &RuntimeCall| true);
+	assert_ok!(remark().dispatch(RuntimeOrigin::signed(1)));
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn constant() {
+	MockFilter::mock_get(|| 32u32);
+	assert_eq!(<MockFilter as Get<u32>>::get(), 32);
+
+	MockFilter::mock_get(|| 64u32);
+	assert_eq!(<MockFilter as Get<u32>>::get(), 64);
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn instance_filter() {
+	MockFilter::mock_filter(|proxy_type, call: &RuntimeCall| match proxy_type.0 {
+		ANY => true,
+		NON_REMARK => call != &remark(),
+		_ => false,
+	});
+
+	assert!(ProxyType::new(ANY).filter(&remark()));
+	assert!(!ProxyType::new(NON_REMARK).filter(&remark()));
+	assert!(ProxyType::new(NON_REMARK).filter(&set_heap_pages()));
+	assert_eq!(ProxyType::default(), ProxyType::new(ANY));
+}
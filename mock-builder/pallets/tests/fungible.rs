@@ -0,0 +1,162 @@
+use frame_support::{
+	assert_err, assert_ok, derive_impl,
+	traits::tokens::{
+		fungible::{
+			freeze::Mutate as _,
+			hold::{Inspect as _, Mutate as _},
+			Inspect as _, Mutate as _,
+		},
+		fungibles::{self, Inspect as _, Mutate as _},
+		Preservation, WithdrawConsequence,
+	},
+};
+use mock_builder_pallets::{pallet_mock_fungible, pallet_mock_fungibles};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{RuntimeDebug, TokenError};
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum HoldReason {
+	Staking,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum FreezeId {
+	Vesting,
+}
+
+frame_support::construct_runtime!(
+	pub struct Runtime {
+		System: frame_system,
+		MockFungible: pallet_mock_fungible,
+		MockFungibles: pallet_mock_fungibles,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = frame_system::mocking::MockBlock<Runtime>;
+}
+
+impl pallet_mock_fungible::Config for Runtime {
+	type Balance = u64;
+	type FreezeId = FreezeId;
+	type HoldReason = HoldReason;
+}
+
+impl pallet_mock_fungibles::Config for Runtime {
+	type AssetId = u32;
+	type Balance = u64;
+	type FreezeId = FreezeId;
+	type HoldReason = HoldReason;
+}
+
+const ALICE: u64 = 1;
+const BOB: u64 = 2;
+const USDC: u32 = 1;
+const DOT: u32 = 2;
+
+#[mock_builder::test(runtime = Runtime)]
+fn mocked_method() {
+	MockFungible::mock_Inspect_balance(|who| match *who {
+		ALICE => 100,
+		_ => 0,
+	});
+
+	assert_eq!(MockFungible::balance(&ALICE), 100);
+	assert_eq!(MockFungible::balance(&BOB), 0);
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn provided_method_from_mocks() {
+	MockFungible::mock_Inspect_can_withdraw(|_, _| WithdrawConsequence::BalanceLow);
+
+	assert_err!(
+		MockFungible::transfer(&ALICE, &BOB, 10, Preservation::Expendable),
+		TokenError::FundsUnavailable
+	);
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn ledger_mint_and_transfer() {
+	MockFungible::use_ledger();
+
+	assert_ok!(MockFungible::mint_into(&ALICE, 100));
+	assert_ok!(MockFungible::transfer(
+		&ALICE,
+		&BOB,
+		30,
+		Preservation::Expendable
+	));
+
+	assert_eq!(MockFungible::balance(&ALICE), 70);
+	assert_eq!(MockFungible::balance(&BOB), 30);
+	assert_eq!(MockFungible::total_issuance(), 100);
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn ledger_hold_and_freeze() {
+	MockFungible::use_ledger();
+
+	assert_ok!(MockFungible::mint_into(&ALICE, 100));
+	assert_ok!(MockFungible::hold(&HoldReason::Staking, &ALICE, 30));
+	assert_ok!(MockFungible::set_freeze(&FreezeId::Vesting, &ALICE, 50));
+
+	assert_eq!(MockFungible::balance(&ALICE), 70);
+	assert_eq!(MockFungible::total_balance(&ALICE), 100);
+	assert_eq!(
+		MockFungible::balance_on_hold(&HoldReason::Staking, &ALICE),
+		30
+	);
+
+	// The freeze also applies to the balance on hold
+	assert_err!(
+		MockFungible::transfer(&ALICE, &BOB, 60, Preservation::Expendable),
+		TokenError::Frozen
+	);
+	assert_ok!(MockFungible::transfer(
+		&ALICE,
+		&BOB,
+		50,
+		Preservation::Expendable
+	));
+
+	assert_ok!(MockFungible::thaw(&FreezeId::Vesting, &ALICE));
+	assert_ok!(MockFungible::transfer(
+		&ALICE,
+		&BOB,
+		20,
+		Preservation::Expendable
+	));
+}
+
+#[mock_builder::test(runtime = Runtime, strict = true)]
+fn ledger_with_mocked_method() {
+	MockFungible::use_ledger();
+	MockFungible::mock_Inspect_minimum_balance(|| 10);
+
+	assert_err!(MockFungible::mint_into(&ALICE, 5), TokenError::BelowMinimum);
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn ledger_by_asset() {
+	MockFungibles::use_ledger();
+
+	assert_ok!(MockFungibles::mint_into(USDC, &ALICE, 100));
+	assert_ok!(MockFungibles::mint_into(DOT, &ALICE, 10));
+	assert_ok!(MockFungibles::transfer(
+		USDC,
+		&ALICE,
+		&BOB,
+		30,
+		Preservation::Expendable
+	));
+
+	assert_eq!(MockFungibles::balance(USDC, &ALICE), 70);
+	assert_eq!(MockFungibles::balance(USDC, &BOB), 30);
+	assert_eq!(MockFungibles::balance(DOT, &ALICE), 10);
+	assert_eq!(MockFungibles::total_issuance(USDC), 100);
+	assert!(<MockFungibles as fungibles::Inspect<u64>>::asset_exists(
+		DOT
+	));
+}
@@ -0,0 +1,85 @@
+use frame_support::{assert_err, assert_ok, derive_impl, traits::EnsureOrigin};
+use frame_system::{ensure_root, ensure_signed};
+use mock_builder_pallets::{pallet_mock_origin, MockOrigin};
+use sp_runtime::traits::BadOrigin;
+
+frame_support::construct_runtime!(
+	pub struct Runtime {
+		System: frame_system,
+		MockAdminOrigin: pallet_mock_origin,
+		MockAssetOrigin: pallet_mock_origin<Instance1>,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = frame_system::mocking::MockBlock<Runtime>;
+}
+
+impl pallet_mock_origin::Config for Runtime {
+	type Success = ();
+}
+
+impl pallet_mock_origin::Config<pallet_mock_origin::Instance1> for Runtime {
+	type Success = u64;
+}
+
+type AdminOrigin = MockOrigin<Runtime>;
+type AssetOrigin = MockOrigin<Runtime, pallet_mock_origin::Instance1>;
+
+const ALICE: u64 = 1;
+const BOB: u64 = 2;
+const USDC: u32 = 1;
+const DOT: u32 = 2;
+
+fn ensure_asset_origin(origin: RuntimeOrigin, asset: u32) -> Result<u64, BadOrigin> {
+	use frame_support::traits::EnsureOriginWithArg;
+
+	<AssetOrigin as EnsureOriginWithArg<_, _>>::ensure_origin(origin, &asset)
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn only_root() {
+	AdminOrigin::mock_EnsureOrigin_try_origin(|origin| {
+		ensure_root(origin.clone()).map_err(|_| origin)
+	});
+
+	assert_ok!(AdminOrigin::ensure_origin(RuntimeOrigin::root()));
+	assert_err!(
+		AdminOrigin::ensure_origin(RuntimeOrigin::signed(ALICE)),
+		BadOrigin
+	);
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn owner_by_arg() {
+	AssetOrigin::mock_EnsureOriginWithArg_try_origin(|origin, asset: &u32| {
+		match ensure_signed(origin.clone()) {
+			Ok(ALICE) if *asset == USDC => Ok(ALICE),
+			_ => Err(origin),
+		}
+	});
+
+	assert_eq!(
+		ensure_asset_origin(RuntimeOrigin::signed(ALICE), USDC),
+		Ok(ALICE)
+	);
+	assert_err!(
+		ensure_asset_origin(RuntimeOrigin::signed(BOB), USDC),
+		BadOrigin
+	);
+	assert_err!(
+		ensure_asset_origin(RuntimeOrigin::signed(ALICE), DOT),
+		BadOrigin
+	);
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+#[mock_builder::test(runtime = Runtime)]
+fn successful_origin() {
+	AdminOrigin::mock_EnsureOrigin_try_successful_origin(|| Ok(RuntimeOrigin::root()));
+	AdminOrigin::mock_EnsureOrigin_try_origin(|_| Ok(()));
+
+	let origin = AdminOrigin::try_successful_origin().unwrap();
+	assert_ok!(AdminOrigin::ensure_origin(origin));
+}
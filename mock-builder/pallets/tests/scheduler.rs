@@ -0,0 +1,117 @@
+use frame_support::{
+	assert_err, assert_ok, derive_impl,
+	traits::{
+		schedule::{
+			v3::{Anon, Named},
+			DispatchTime,
+		},
+		Bounded, StorePreimage,
+	},
+};
+use frame_system::RawOrigin;
+use mock_builder_pallets::pallet_mock_scheduler;
+use sp_runtime::DispatchError;
+
+frame_support::construct_runtime!(
+	pub struct Runtime {
+		System: frame_system,
+		MockScheduler: pallet_mock_scheduler,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = frame_system::mocking::MockBlock<Runtime>;
+}
+
+impl pallet_mock_scheduler::Config for Runtime {
+	type PalletsOrigin = OriginCaller;
+}
+
+const KEY: &[u8] = b"key";
+const NAME: [u8; 32] = [1; 32];
+
+fn set_storage(value: Vec<u8>) -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::set_storage {
+		items: vec![(KEY.to_vec(), value)],
+	})
+}
+
+fn root() -> OriginCaller {
+	OriginCaller::system(RawOrigin::Root)
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn mocked_method() {
+	MockScheduler::mock_Anon_schedule(|_, _, _, _, _| Err(DispatchError::Exhausted));
+
+	assert_err!(
+		MockScheduler::schedule(
+			DispatchTime::At(10),
+			None,
+			0,
+			root(),
+			Bounded::Inline(Default::default()),
+		),
+		DispatchError::Exhausted
+	);
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn record_and_fire() {
+	MockScheduler::use_agenda();
+	System::set_block_number(5);
+
+	let call = MockScheduler::bound(set_storage(vec![42])).unwrap();
+	let id = MockScheduler::schedule(DispatchTime::After(10), None, 0, root(), call).unwrap();
+
+	let (_, task) = MockScheduler::tasks().pop().unwrap();
+	assert_eq!(task.when, 16);
+	assert_eq!(task.origin, root());
+
+	assert_ok!(MockScheduler::fire(id));
+	assert_eq!(sp_io::storage::get(KEY).as_deref(), Some(&[42][..]));
+	assert!(MockScheduler::tasks().is_empty());
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn fire_from_preimage() {
+	MockScheduler::use_agenda();
+	MockScheduler::use_preimages();
+
+	// Too long to be inlined
+	let call = MockScheduler::bound(set_storage(vec![42; 200])).unwrap();
+	assert!(matches!(call, Bounded::Lookup { .. }));
+
+	let id = MockScheduler::schedule(DispatchTime::At(10), None, 0, root(), call).unwrap();
+
+	assert_ok!(MockScheduler::fire(id));
+	assert_eq!(sp_io::storage::get(KEY).as_deref(), Some(&[42; 200][..]));
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn named_task() {
+	MockScheduler::use_agenda();
+
+	let call = MockScheduler::bound(set_storage(vec![42])).unwrap();
+	assert_ok!(MockScheduler::schedule_named(
+		NAME,
+		DispatchTime::At(10),
+		None,
+		0,
+		root(),
+		call
+	));
+
+	assert_ok!(MockScheduler::reschedule_named(NAME, DispatchTime::At(20)));
+	assert_eq!(
+		<MockScheduler as Named<_, _, _>>::next_dispatch_time(NAME),
+		Ok(20)
+	);
+
+	assert_ok!(MockScheduler::cancel_named(NAME));
+	assert_err!(
+		<MockScheduler as Named<_, _, _>>::next_dispatch_time(NAME),
+		DispatchError::Unavailable
+	);
+}
@@ -0,0 +1,65 @@
+use frame_support::{
+	derive_impl,
+	traits::{EstimateNextSessionRotation, Randomness, Time, UnixTime},
+	weights::Weight,
+};
+use mock_builder_pallets::pallet_mock_time;
+use sp_runtime::testing::H256;
+
+frame_support::construct_runtime!(
+	pub struct Runtime {
+		System: frame_system,
+		MockTime: pallet_mock_time,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = frame_system::mocking::MockBlock<Runtime>;
+}
+
+impl pallet_mock_time::Config for Runtime {
+	type Moment = u64;
+}
+
+#[mock_builder::test(runtime = Runtime, strict = true)]
+fn set_now() {
+	MockTime::set_now(1_500);
+	assert_eq!(<MockTime as Time>::now(), 1_500);
+
+	MockTime::set_now(3_000);
+	assert_eq!(<MockTime as Time>::now(), 3_000);
+	assert_eq!(<MockTime as UnixTime>::now().as_secs(), 3);
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn set_now_over_mocks() {
+	MockTime::mock_Time_now(|| 42);
+	assert_eq!(<MockTime as Time>::now(), 42);
+
+	MockTime::set_now(1_500);
+	assert_eq!(<MockTime as Time>::now(), 1_500);
+
+	MockTime::clear_now();
+	assert_eq!(<MockTime as Time>::now(), 42);
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn randomness() {
+	MockTime::mock_Randomness_random(|subject| (H256::repeat_byte(subject.len() as u8), 10));
+
+	assert_eq!(MockTime::random(b"seed"), (H256::repeat_byte(4), 10));
+	assert_eq!(MockTime::random_seed(), (H256::zero(), 10));
+}
+
+#[mock_builder::test(runtime = Runtime)]
+fn session_rotation() {
+	MockTime::mock_EstimateNextSessionRotation_estimate_next_session_rotation(|now| {
+		(Some((now / 100 + 1) * 100), Weight::zero())
+	});
+
+	assert_eq!(
+		MockTime::estimate_next_session_rotation(42),
+		(Some(100), Weight::zero())
+	);
+}
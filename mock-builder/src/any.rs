@@ -0,0 +1,54 @@
+//! Mocks registered once for all the instantiations of a generic method. The
+//! closure receives the name of the output type expected by each execution
+//! and returns a boxed value of that type:
+//!
+//! ```ignore
+//! impl<T: Config> Pallet<T> {
+//!     pub fn mock_balance_any(f: impl Fn(&str) -> Box<dyn Any> + 'static) {
+//!         register_call_any!(f);
+//!     }
+//! }
+//!
+//! impl<T: Config> Currencies for Pallet<T> {
+//!     fn balance<C: Currency>(who: &T::AccountId) -> C::Balance {
+//!         execute_call!(who, any)
+//!     }
+//! }
+//!
+//! MockCurrencies::mock_balance_any(|type_name| match type_name {
+//!     "u64" => Box::new(100u64),
+//!     _ => Box::new(100u128),
+//! });
+//! ```
+//!
+//! The register function must have the name of the mocked method with the
+//! `mock_` prefix and the `_any` suffix. The input of the executions is not
+//! given to the closure. A mock registered for the types of an execution takes
+//! precedence over the one registered for any type.
+//!
+//! Only the executions called as `execute_call!(input, any)` look for the
+//! mocks registered for any type, because the boxed value is unboxed by its
+//! `TypeId`, which requires a `'static` output type.
+
+use alloc::boxed::Box;
+use core::any::Any;
+
+/// Function unboxing the value returned by a mock registered for any type.
+pub type Downcast<O> = fn(Box<dyn Any>) -> Result<O, Box<dyn Any>>;
+
+/// Unbox the value as `O`, or return it back if it's of other type.
+pub fn downcast<O: 'static>(value: Box<dyn Any>) -> Result<O, Box<dyn Any>> {
+	value.downcast::<O>().map(|value| *value)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn downcasted() {
+		assert_eq!(downcast::<u64>(Box::new(42u64)).ok(), Some(42));
+		assert_eq!(downcast::<&str>(Box::new("a")).ok(), Some("a"));
+		assert!(downcast::<u32>(Box::new(42u64)).is_err());
+	}
+}
@@ -0,0 +1,42 @@
+/// Mocks a runtime registers by default to run the benchmarks of its pallets
+/// as tests, i.e. with `impl_benchmark_test_suite!()`. The benchmarks don't
+/// know about mocks, so these defaults must satisfy any benchmark.
+///
+/// ```ignore
+/// impl mock_builder::BenchmarkDefaults for Runtime {
+///     fn register_defaults() {
+///         MockHandler::mock_pre_dispatch_check(|_| Ok(()));
+///     }
+/// }
+///
+/// impl_benchmark_test_suite!(
+///     Pallet,
+///     mock_builder::benchmark_externalities!(crate::mock::Runtime),
+///     crate::mock::Runtime
+/// );
+/// ```
+pub trait BenchmarkDefaults {
+	/// Register the default mocks. Called inside the externalities.
+	fn register_defaults();
+}
+
+/// Build the externalities for the benchmark tests of `$runtime`, with its
+/// [`BenchmarkDefaults`] registered and committed into its storage.
+/// By default, the externalities are built with
+/// `frame_system::Pallet::externalities()`, but any other can be given as
+/// second argument.
+#[macro_export]
+macro_rules! benchmark_externalities {
+	($runtime:ty) => {
+		$crate::benchmark_externalities!(
+			$runtime,
+			$crate::__private::frame_system::Pallet::<$runtime>::externalities()
+		)
+	};
+	($runtime:ty, $ext:expr) => {{
+		let mut ext = $ext;
+		ext.execute_with(<$runtime as $crate::BenchmarkDefaults>::register_defaults);
+		ext.commit_all().expect("Benchmark defaults can be committed");
+		ext
+	}};
+}
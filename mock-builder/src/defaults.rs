@@ -0,0 +1,168 @@
+//! Default outputs of the mocks of a *mock pallet*, returned by the methods
+//! without a registered mock, so a test only registers the mocks of the
+//! methods it cares about. Each method declares its default output in its
+//! execute macro, and the pallet exposes a switch to use them:
+//!
+//! ```ignore
+//! impl<T: Config> Pallet<T> {
+//!     pub fn use_defaults() {
+//!         mock_builder::defaults::enable_for::<Self>();
+//!     }
+//! }
+//!
+//! impl<T: Config> TraitA for Pallet<T> {
+//!     fn foo(a: String, b: u64) -> DispatchResult {
+//!         execute_call!((a, b), default = Ok(()))
+//!     }
+//!
+//!     fn bar() -> u64 {
+//!         execute_call!((), default = Default::default())
+//!     }
+//! }
+//! ```
+//!
+//! A test calling `MockDep::use_defaults()` obtains `Ok(())` from `foo()`
+//! unless it registers a mock for it. The defaults are used until
+//! [`clear()`](crate::clear) is called. Without calling `use_defaults()`, an
+//! execution without a registered mock panics as usual.
+
+use std::cell::RefCell;
+
+use super::location::FunctionLocation;
+
+thread_local! {
+	static PALLETS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Use the default outputs of the mock pallet `P` in the current thread,
+/// i.e: `enable_for::<MockDep>()`.
+pub fn enable_for<P>() {
+	let pallet = std::any::type_name::<P>().to_owned();
+	PALLETS.with(|pallets| pallets.borrow_mut().push(pallet));
+}
+
+/// Check if the default outputs of the mock pallet `P` are used in the
+/// current thread.
+pub fn is_enabled_for<P>() -> bool {
+	let pallet = std::any::type_name::<P>();
+	PALLETS.with(|pallets| pallets.borrow().iter().any(|enabled| enabled == pallet))
+}
+
+pub(crate) fn is_enabled_at(location: &FunctionLocation) -> bool {
+	PALLETS.with(|pallets| {
+		pallets
+			.borrow()
+			.iter()
+			.any(|pallet| location.belongs_to(pallet))
+	})
+}
+
+pub(crate) fn clear_for(pallet: &str) {
+	PALLETS.with(|pallets| pallets.borrow_mut().retain(|enabled| enabled != pallet));
+}
+
+pub(crate) fn clear() {
+	PALLETS.with(|pallets| pallets.borrow_mut().clear());
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	trait Oracle {
+		fn price(asset: u32) -> u64;
+		fn name(asset: u32) -> String;
+	}
+
+	struct MockOracle;
+
+	impl MockOracle {
+		fn use_defaults() {
+			enable_for::<Self>();
+		}
+
+		fn mock_price(f: impl Fn(u32) -> u64 + 'static) {
+			crate::register_call_local!(f);
+		}
+	}
+
+	impl Oracle for MockOracle {
+		fn price(asset: u32) -> u64 {
+			crate::execute_call_local!(asset, default = 1)
+		}
+
+		fn name(asset: u32) -> String {
+			crate::execute_call_local!(asset, default = Default::default())
+		}
+	}
+
+	struct OtherOracle;
+
+	impl Oracle for OtherOracle {
+		fn price(asset: u32) -> u64 {
+			crate::execute_call_local!(asset, default = 2)
+		}
+
+		fn name(asset: u32) -> String {
+			crate::execute_call_local!(asset)
+		}
+	}
+
+	#[test]
+	fn default_outputs() {
+		MockOracle::use_defaults();
+
+		assert!(is_enabled_for::<MockOracle>());
+		assert_eq!(MockOracle::price(3), 1);
+		assert_eq!(MockOracle::name(3), "");
+	}
+
+	#[test]
+	fn registered_over_default() {
+		MockOracle::use_defaults();
+		MockOracle::mock_price(|asset| asset as u64 * 10);
+
+		assert_eq!(MockOracle::price(3), 30);
+	}
+
+	#[test]
+	fn recorded_in_golden_files() {
+		MockOracle::use_defaults();
+		crate::golden::start();
+		MockOracle::price(3);
+
+		assert_eq!(
+			crate::golden::interactions(),
+			vec![crate::golden::Interaction {
+				method: "Oracle::price".into(),
+				mock: "MockOracle".into(),
+				input: Some("3".into()),
+				output: Some("1".into()),
+			}]
+		);
+	}
+
+	#[test]
+	#[should_panic(expected = "Mock was not found")]
+	fn not_enabled() {
+		MockOracle::price(3);
+	}
+
+	#[test]
+	#[should_panic(expected = "Mock was not found")]
+	fn enabled_for_other_pallet() {
+		MockOracle::use_defaults();
+
+		assert!(!is_enabled_for::<OtherOracle>());
+		OtherOracle::price(3);
+	}
+
+	#[test]
+	#[should_panic(expected = "Mock was not found")]
+	fn cleared() {
+		MockOracle::use_defaults();
+		crate::clear();
+
+		MockOracle::price(3);
+	}
+}
@@ -0,0 +1,30 @@
+use alloc::vec::Vec;
+
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::{hashing::blake2_256, H256};
+
+/// Event deposited by a *mock pallet* each time one of its mocks is executed.
+/// The pallet event should be convertible from this type.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub enum MockEvent {
+	/// A mock was called.
+	Called {
+		/// Name of the method called, prefixed by the trait name if it belongs
+		/// to a trait, i.e: `TraitA::foo`.
+		method: Vec<u8>,
+
+		/// Hash of the SCALE encoding of the input.
+		input_hash: H256,
+	},
+}
+
+impl MockEvent {
+	/// Event expected when `method` is called with `input`.
+	pub fn called<I: Encode>(method: &str, input: &I) -> Self {
+		Self::Called {
+			method: method.as_bytes().to_vec(),
+			input_hash: input.using_encoded(blake2_256).into(),
+		}
+	}
+}
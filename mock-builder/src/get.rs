@@ -0,0 +1,107 @@
+use core::marker::PhantomData;
+
+use sp_core::Get;
+
+#[cfg(feature = "std")]
+use super::{
+	local::LocalRegistry,
+	location::{FunctionLocation, TraitInfo},
+	storage,
+};
+
+/// Parameter type whose value can be mocked by each test. While it's not
+/// mocked, it resolves to the value of `Marker`, usually the parameter type
+/// declared with `parameter_types!`:
+///
+/// ```ignore
+/// parameter_types! {
+///     pub const MaxRemarksPerCall: u32 = 3;
+/// }
+///
+/// impl pallet_remarks::Config for Runtime {
+///     type MaxRemarksPerCall = MockGet<MaxRemarksPerCall, u32>;
+/// }
+///
+/// #[mock_builder::test(runtime = Runtime)]
+/// fn single_remark() {
+///     MockGet::<MaxRemarksPerCall, u32>::set(1);
+///     // ...
+/// }
+/// ```
+///
+/// The mocked value is registered as any other mock, so it's affected by the
+/// [`strict`](crate::strict) mode and removed by [`clear()`](crate::clear()).
+/// Without the `std` feature, it always resolves to the value of `Marker`.
+pub struct MockGet<Marker, T>(PhantomData<(Marker, T)>);
+
+#[cfg(feature = "std")]
+impl<Marker: Get<T> + 'static, T: 'static> MockGet<Marker, T> {
+	/// Register the closure that resolves the value.
+	pub fn mock_get(f: impl Fn() -> T + 'static) {
+		let f = move |()| f();
+		let reprs = crate::debug_reprs!(&f);
+		crate::register(|| (), f, reprs, LocalRegistry::insert);
+	}
+
+	/// Resolve the value to `value`.
+	pub fn set(value: T)
+	where
+		T: Clone,
+	{
+		Self::mock_get(move || value.clone())
+	}
+}
+
+#[cfg(feature = "std")]
+impl<Marker: Get<T>, T> Get<T> for MockGet<Marker, T> {
+	fn get() -> T {
+		let location = FunctionLocation::from(|| ())
+			.normalize()
+			.append_type_signature::<(), T>();
+
+		let call_id = LocalRegistry::get(location.get(TraitInfo::Whatever))
+			.or_else(|| LocalRegistry::get(location.get(TraitInfo::No)));
+
+		match call_id {
+			Some(call_id) => storage::execute_call(call_id, ()).unwrap_or_else(|err| {
+				panic!("{err}. Location: {location:?}");
+			}),
+			None => Marker::get(),
+		}
+	}
+}
+
+#[cfg(not(feature = "std"))]
+impl<Marker: Get<T>, T> Get<T> for MockGet<Marker, T> {
+	fn get() -> T {
+		Marker::get()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use sp_core::ConstU32;
+
+	use super::*;
+
+	type MaxItems = MockGet<ConstU32<3>, u32>;
+	type MinItems = MockGet<ConstU32<1>, u32>;
+
+	#[test]
+	fn not_mocked() {
+		assert_eq!(MaxItems::get(), 3);
+	}
+
+	#[test]
+	fn mocked() {
+		MaxItems::set(0);
+		assert_eq!(MaxItems::get(), 0);
+		assert_eq!(MinItems::get(), 1);
+
+		MaxItems::mock_get(|| 5);
+		assert_eq!(MaxItems::get(), 5);
+
+		crate::clear();
+		assert_eq!(MaxItems::get(), 3);
+	}
+}
@@ -0,0 +1,265 @@
+//! Golden files of the interactions with the mocked dependencies. Instead of
+//! asserting each call of a complex flow, the whole log of mock executions of
+//! a test is compared against a checked-in file:
+//!
+//! ```ignore
+//! #[mock_builder::test(runtime = Runtime, golden = "tests/golden/transfer.txt")]
+//! fn transfer() {
+//!     MockDep::mock_foo(|| true);
+//!     MyPallet::my_call();
+//! }
+//! ```
+//!
+//! Each execution is written with its method, its mock and the `Debug`
+//! representations of its input and output:
+//!
+//! ```text
+//! TraitA::foo
+//!   mock: pallet_mock_dep
+//!   input: ("hello", Some(42))
+//!   output: true
+//! ```
+//!
+//! A mismatch panics with a line diff against the golden file. Running the
+//! tests with the [`BLESS_VAR`](crate::golden::BLESS_VAR) environment variable
+//! set writes the current log to the golden files instead:
+//!
+//! ```sh
+//! MOCK_BUILDER_BLESS=1 cargo test
+//! ```
+//!
+//! The paths given to the [`macro@crate::test`] attribute are relative to the
+//! package root. The ones given to
+//! [`assert_golden()`](crate::golden::assert_golden) are relative to the
+//! current directory, which is also the package root when running `cargo test`.
+
+use std::{
+	cell::{Cell, RefCell},
+	fmt, fs,
+	path::Path,
+};
+
+use super::{location::FunctionLocation, util::NO_DEBUG};
+
+/// Environment variable to write the golden files instead of checking them.
+pub const BLESS_VAR: &str = "MOCK_BUILDER_BLESS";
+
+thread_local! {
+	static RECORDING: Cell<bool> = const { Cell::new(false) };
+	static INTERACTIONS: RefCell<Vec<Interaction>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Execution of a mock.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Interaction {
+	/// Name of the executed method, prefixed by the trait name if known.
+	pub method: String,
+
+	/// Name of the mock, i.e: the module of a *mock pallet*. The paths and
+	/// generics of the mock type are left out, since their representation
+	/// depends on the compiler version.
+	pub mock: String,
+
+	/// `Debug` representation of the input, if its type implements it.
+	pub input: Option<String>,
+
+	/// `Debug` representation of the output, if its type implements it.
+	pub output: Option<String>,
+}
+
+impl fmt::Display for Interaction {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "{}", self.method)?;
+		writeln!(f, "  mock: {}", self.mock)?;
+		writeln!(f, "  input: {}", self.input.as_deref().unwrap_or(NO_DEBUG))?;
+		writeln!(
+			f,
+			"  output: {}",
+			self.output.as_deref().unwrap_or(NO_DEBUG)
+		)
+	}
+}
+
+/// Start recording the mock executions of the current thread, until
+/// [`clear()`](crate::clear) is called.
+pub fn start() {
+	RECORDING.with(|recording| recording.set(true));
+}
+
+/// Mock executions recorded in the current thread.
+pub fn interactions() -> Vec<Interaction> {
+	INTERACTIONS.with(|interactions| interactions.borrow().clone())
+}
+
+/// Text representation of the mock executions recorded in the current thread.
+pub fn render() -> String {
+	interactions().iter().map(Interaction::to_string).collect()
+}
+
+/// Check the mock executions recorded in the current thread against the
+/// golden file, panicking with a diff if they mismatch. If the [`BLESS_VAR`]
+/// environment variable is set, the golden file is written instead.
+pub fn assert_golden(path: impl AsRef<Path>) {
+	let path = path.as_ref();
+	let actual = render();
+
+	if std::env::var_os(BLESS_VAR).is_some() {
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent).unwrap_or_else(|err| {
+				panic!("Golden file directory can not be created: {err}. Path: {path:?}")
+			});
+		}
+
+		return fs::write(path, actual)
+			.unwrap_or_else(|err| panic!("Golden file can not be written: {err}. Path: {path:?}"));
+	}
+
+	let expected = fs::read_to_string(path).unwrap_or_else(|err| {
+		panic!("Golden file can not be read: {err}. Run with {BLESS_VAR}=1 to write it. Path: {path:?}")
+	});
+
+	if expected != actual {
+		panic!(
+			"Mock interactions mismatch the golden file. Run with {BLESS_VAR}=1 to update it. Path: {path:?}\n{}",
+			diff(&expected, &actual)
+		);
+	}
+}
+
+/// Record the execution of a mock, if recording.
+pub(crate) fn record(
+	location: &FunctionLocation,
+	input: Option<String>,
+	output: impl FnOnce() -> Option<String>,
+) {
+	if !RECORDING.with(Cell::get) {
+		return;
+	}
+
+	let interaction = Interaction {
+		method: location.method(),
+		mock: location.mock_name().into(),
+		input,
+		output: output(),
+	};
+
+	INTERACTIONS.with(|interactions| interactions.borrow_mut().push(interaction));
+}
+
+/// Stop recording and forget the mock executions of the current thread.
+pub(crate) fn clear() {
+	RECORDING.with(|recording| recording.set(false));
+	INTERACTIONS.with(|interactions| interactions.borrow_mut().clear());
+}
+
+/// Line diff from `expected` to `actual`, based on their longest common
+/// subsequence. Removed lines are prefixed with `-` and added ones with `+`.
+fn diff(expected: &str, actual: &str) -> String {
+	let expected = expected.lines().collect::<Vec<_>>();
+	let actual = actual.lines().collect::<Vec<_>>();
+
+	// `lcs[i][j]` is the length of the longest common subsequence of
+	// `expected[i..]` and `actual[j..]`.
+	let mut lcs = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+	for i in (0..expected.len()).rev() {
+		for j in (0..actual.len()).rev() {
+			lcs[i][j] = match expected[i] == actual[j] {
+				true => lcs[i + 1][j + 1] + 1,
+				false => lcs[i + 1][j].max(lcs[i][j + 1]),
+			};
+		}
+	}
+
+	let mut lines = Vec::new();
+	let (mut i, mut j) = (0, 0);
+	while i < expected.len() || j < actual.len() {
+		if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+			lines.push(format!("  {}", expected[i]));
+			(i, j) = (i + 1, j + 1);
+		} else if i < expected.len() && (j == actual.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+			lines.push(format!("- {}", expected[i]));
+			i += 1;
+		} else {
+			lines.push(format!("+ {}", actual[j]));
+			j += 1;
+		}
+	}
+
+	lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	trait Oracle {
+		fn price(asset: u32) -> u64;
+	}
+
+	struct MockOracle;
+
+	impl MockOracle {
+		fn mock_price(f: impl Fn(u32) -> u64 + 'static) {
+			crate::register_call_local!(f);
+		}
+	}
+
+	impl Oracle for MockOracle {
+		fn price(asset: u32) -> u64 {
+			crate::execute_call_local!(asset)
+		}
+	}
+
+	const MOCK: &str = "MockOracle";
+
+	#[test]
+	fn recorded() {
+		MockOracle::mock_price(|asset| asset as u64 * 10);
+		MockOracle::price(1);
+
+		start();
+		MockOracle::price(2);
+
+		assert_eq!(
+			interactions(),
+			vec![Interaction {
+				method: "Oracle::price".into(),
+				mock: MOCK.into(),
+				input: Some("2".into()),
+				output: Some("20".into()),
+			}]
+		);
+
+		assert_eq!(
+			render(),
+			format!("Oracle::price\n  mock: {MOCK}\n  input: 2\n  output: 20\n")
+		);
+	}
+
+	#[test]
+	fn cleared() {
+		MockOracle::mock_price(|_| 1);
+		start();
+		MockOracle::price(0);
+
+		crate::clear();
+		MockOracle::mock_price(|_| 1);
+		MockOracle::price(0);
+
+		assert!(interactions().is_empty());
+	}
+
+	#[test]
+	fn matched_golden_file() {
+		MockOracle::mock_price(|asset| asset as u64 * 10);
+		start();
+		MockOracle::price(3);
+
+		assert_golden(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/oracle_price.txt"));
+	}
+
+	#[test]
+	fn diffed() {
+		assert_eq!(diff("a\nb\nc\n", "a\nx\nc\nd\n"), "  a\n- b\n+ x\n  c\n+ d");
+	}
+}
@@ -0,0 +1,34 @@
+//! The output of a mock must be a concrete type, so methods returning
+//! `impl Iterator` are mocked with closures returning a [`MockIter`], which
+//! the trait implementation annotates and returns as its iterator:
+//!
+//! ```ignore
+//! impl<T: Config> Pallet<T> {
+//!     pub fn mock_values(f: impl for<'a> Fn(&'a T::Key) -> MockIter<'a, u32> + 'static) {
+//!         register_call!(f);
+//!     }
+//! }
+//!
+//! impl<T: Config> TraitA for Pallet<T> {
+//!     fn values(key: &T::Key) -> impl Iterator<Item = u32> + '_ {
+//!         let values: MockIter<u32> = execute_call!(key);
+//!         values
+//!     }
+//! }
+//!
+//! MockA::mock_values(|_| mock_builder::iter::boxed([1, 2, 3]));
+//! ```
+
+use alloc::boxed::Box;
+
+/// Boxed iterator returned by the mocks of methods returning `impl Iterator`.
+pub type MockIter<'a, T> = Box<dyn Iterator<Item = T> + 'a>;
+
+/// Box the iterator of `iter` as a [`MockIter`].
+pub fn boxed<'a, I>(iter: I) -> MockIter<'a, I::Item>
+where
+	I: IntoIterator,
+	I::IntoIter: 'a,
+{
+	Box::new(iter.into_iter())
+}
@@ -11,6 +11,8 @@
 // MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
 // GNU General Public License for more details.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
 //! `mock-builder` allows you to create *mock pallets*.
 //! A *mock pallet* is a regular pallet that implements some traits whose
 //! behavior can be implemented on the fly by closures. They are perfect for
@@ -126,9 +128,20 @@
 //!
 //! ## Mock pallet creation
 //!
-//! **NOTE: There is a working progress on this part to generate *mock pallets*
-//! automatically using procedural macros. Once done, all this part can be
-//! auto-generated.**
+//! **NOTE: The `mock-builder` binary of the `mock-builder-codegen` package
+//! writes this part for you, generating the *mock pallet* module from the
+//! files where the traits are defined:**
+//!
+//! ```sh
+//! cargo run -p mock-builder-codegen -- --trait TraitA --trait TraitB \
+//!     --output src/pallet_mock_traits.rs src/traits.rs
+//! ```
+//!
+//! The trait generics and associated types become `Config` types, methods
+//! with the same name in several traits are mocked by `mock_<Trait>_<method>`
+//! functions, and `--instance` generates an instantiable *mock pallet*. Methods
+//! with a `self` receiver are not supported, see
+//! [Mock objects](#mock-objects) instead.
 //!
 //! This crate exports two macros [`register_call!()`] and [`execute_call!()`]
 //! that allow you to build a *mock pallet*.
@@ -221,6 +234,273 @@
 //!
 //! If types for the closure of `mock_*` method and trait method don't match,
 //! you will obtain a runtime error in your tests.
+//! Their lifetimes are not checked, so a closure taking or returning
+//! references must be valid for any lifetime, as `impl Fn(&u8) -> &u8` with
+//! elided lifetimes is. A `mock_*` method must never take a closure with a
+//! fixed lifetime, as `impl Fn(&'static u8)`, which would be given shorter
+//! borrows.
+//!
+//! ## Instantiable mock pallets
+//!
+//! A *mock pallet* can be generic over an instance, so a runtime uses it for
+//! several dependencies implementing the same trait. The instance is part of
+//! the location of its mocks, and each instance has its own `CallIds`
+//! storage, so the mocks registered for one instance are never executed by
+//! another one. The `*_instance` variants of the macros use
+//! `CallIds::<T, I>`:
+//!
+//! ```ignore
+//! #[frame_support::pallet(dev_mode)]
+//! pub mod pallet_mock_oracle {
+//!     use frame_support::pallet_prelude::*;
+//!     use mock_builder::{execute_call_instance, register_call_instance};
+//!
+//!     #[pallet::config]
+//!     pub trait Config<I: 'static = ()>: frame_system::Config {}
+//!
+//!     #[pallet::pallet]
+//!     pub struct Pallet<T, I = ()>(_);
+//!
+//!     #[pallet::storage]
+//!     type CallIds<T: Config<I>, I: 'static = ()> =
+//!         StorageMap<_, _, String, mock_builder::CallId>;
+//!
+//!     impl<T: Config<I>, I: 'static> Pallet<T, I> {
+//!         pub fn mock_price(f: impl Fn(AssetId) -> Balance + 'static) {
+//!             register_call_instance!(f);
+//!         }
+//!     }
+//!
+//!     impl<T: Config<I>, I: 'static> Oracle for Pallet<T, I> {
+//!         fn price(asset: AssetId) -> Balance {
+//!             execute_call_instance!(asset)
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Each instance is added to the runtime and configured separately:
+//!
+//! ```ignore
+//! frame_support::construct_runtime!(
+//!     pub struct Runtime {
+//!         System: frame_system,
+//!         MockOracle1: pallet_mock_oracle::<Instance1>,
+//!         MockOracle2: pallet_mock_oracle::<Instance2>,
+//!         MyPallet: my_pallet,
+//!     }
+//! );
+//!
+//! impl pallet_mock_oracle::Config<pallet_mock_oracle::Instance1> for Runtime {}
+//! impl pallet_mock_oracle::Config<pallet_mock_oracle::Instance2> for Runtime {}
+//! ```
+//!
+//! The functions taking a pallet type, as [`registered_mocks_for()`],
+//! [`unused_mocks_for()`], [`clear_for()`], [`strict::enable_for()`] or
+//! [`defaults::enable_for()`], only apply to the given instance, i.e:
+//! `clear_for::<MockOracle1>()` keeps the mocks of `MockOracle2`.
+//!
+//! ## Dispatchable mock calls
+//!
+//! Pallets that wrap or filter calls (proxies, batches, remarks, ...) need an
+//! inner call to dispatch. Instead of building real calls from other pallets,
+//! a *mock pallet* can optionally expose a `mock_dispatch` call whose behavior
+//! is registered per `id` with [`register_dispatch!()`] and executed with
+//! [`execute_dispatch!()`]:
+//!
+//! ```ignore
+//! impl<T: Config> Pallet<T> {
+//!     pub fn mock_dispatch_with(
+//!         id: u32,
+//!         f: impl Fn(OriginFor<T>) -> DispatchResultWithPostInfo + 'static,
+//!     ) {
+//!         register_dispatch!(id, f)
+//!     }
+//! }
+//!
+//! #[pallet::call]
+//! impl<T: Config> Pallet<T> {
+//!     #[pallet::call_index(0)]
+//!     pub fn mock_dispatch(origin: OriginFor<T>, id: u32) -> DispatchResultWithPostInfo {
+//!         execute_dispatch!(id, origin)
+//!     }
+//! }
+//! ```
+//!
+//! Then, any outcome can be obtained from the inner call: `Ok`, a specific
+//! `DispatchError`, an actual weight, `Pays::No` or even a nested dispatch.
+//!
+//! ```ignore
+//! MockDep::mock_dispatch_with(0, |origin| {
+//!     ensure_root(origin)?;
+//!     Ok(Pays::No.into())
+//! });
+//!
+//! let call = RuntimeCall::MockDep(pallet_mock_dep::Call::mock_dispatch { id: 0 });
+//! ```
+//!
+//! ## Mock events
+//!
+//! A *mock pallet* can opt in to deposit a [`MockEvent::Called`] each time a
+//! mock is executed by using [`execute_call_with_event!()`] instead of
+//! [`execute_call!()`]. It only requires a pallet event convertible from
+//! [`MockEvent`], and inputs implementing `Encode`, whose hash is part of the
+//! event:
+//!
+//! ```ignore
+//! #[pallet::event]
+//! #[pallet::generate_deposit(pub(super) fn deposit_event)]
+//! pub enum Event<T: Config> {
+//!     Mock(mock_builder::MockEvent),
+//! }
+//!
+//! impl<T: Config> From<mock_builder::MockEvent> for Event<T> {
+//!     fn from(event: mock_builder::MockEvent) -> Self {
+//!         Event::Mock(event)
+//!     }
+//! }
+//! ```
+//!
+//! Then, the dependency interactions can be asserted together with the events
+//! of the real pallets:
+//!
+//! ```ignore
+//! MyPallet::my_call();
+//!
+//! System::assert_has_event(
+//!     pallet_mock_dep::Event::Mock(MockEvent::called("TraitB::bar", &(42u64, 23u8))).into(),
+//! );
+//! ```
+//!
+//! ## Mocks for any type
+//!
+//! Generic methods need a mock for each instantiation, since the types are
+//! part of the mock location. [`register_call_any!()`] registers one mock for
+//! all of them, whose closure returns a boxed value of the expected type,
+//! for the methods executing their mocks with `execute_call!(input, any)`.
+//! See [`any`] for an example.
+//!
+//! ## Mocks without externalities
+//!
+//! The macros above store the mocks in the pallet storage, so they need a
+//! runtime and externalities. [`register_call_local!()`] and
+//! [`execute_call_local!()`] store them in the current thread instead, with
+//! the [`LocalRegistry`], so any struct can be a mock in ordinary tests. See
+//! [`local`] for an example.
+//!
+//! ## Mock objects
+//!
+//! Traits with `self` receivers are mocked by plain structs declared with
+//! [`mock_object!()`], whose instances carry an id. Their mocks are
+//! registered with [`register_object_call!()`] and executed with
+//! [`execute_object_call!()`], for all their instances or for one of them.
+//! See [`object`] for an example.
+//!
+//! ## Test attribute
+//!
+//! Instead of wrapping each test body with
+//! `System::externalities().execute_with(|| { ... })`, you can use the
+//! [`macro@test`] attribute, which also verifies and clears the mocks once the
+//! test finishes:
+//!
+//! ```ignore
+//! #[mock_builder::test(runtime = Runtime, block = 10)]
+//! fn correct() {
+//!     MockDep::mock_foo(|| true);
+//!     MyPallet::my_call();
+//! }
+//! ```
+//!
+//! ## Strict mode
+//!
+//! Overwriting a mock or never executing it usually means the test no longer
+//! exercises what it was written for. In [`strict`] mode, registering a mock
+//! over an active one panics unless it's done inside [`replace()`], and
+//! [`verify()`] panics reporting the location of any mock never executed.
+//! Mocks registered inside [`strict::lax()`], like default behaviors, are
+//! excluded from these checks.
+//! It can be enabled for all mock pallets or only for some of them:
+//!
+//! ```ignore
+//! #[mock_builder::test(runtime = Runtime, strict = true)]
+//! fn correct() {
+//!     // ...
+//! }
+//!
+//! #[mock_builder::test(runtime = Runtime)]
+//! fn correct_with_strict_dep() {
+//!     mock_builder::strict::enable_for::<MockDep>();
+//!     // ...
+//! }
+//! ```
+//!
+//! ## Tracing
+//!
+//! With the `tracing` feature enabled, a trace event is emitted each time a
+//! mock is registered or executed, with its location, type signature and
+//! [`CallId`], and the `Debug` representation of the input and output when
+//! their types implement it. The [`macro@test`] attribute initializes a
+//! subscriber for them, so the whole interaction with the mocked dependencies
+//! of a test can be shown with:
+//!
+//! ```sh
+//! RUST_LOG=mock_builder=trace cargo test --features mock-builder/tracing
+//! ```
+//!
+//! ## `no_std` builds
+//!
+//! Mocks are only registered and executed with the `std` feature, enabled by
+//! default. Without it, *mock pallets* still compile, so they can be part of a
+//! runtime also built for `wasm`, but the register macros do nothing and the
+//! execute macros panic. They can return an output instead, e.g. an error:
+//!
+//! ```ignore
+//! execute_call!((a, b), no_std = Err(DispatchError::Unavailable))
+//! ```
+//!
+//! ## Default outputs
+//!
+//! Instead of registering a mock for each method of a dependency, a test can
+//! use the default outputs of a *mock pallet*, i.e. `Ok(())` or
+//! `Default::default()`, and register only the mocks of the methods under
+//! test. Each method declares its default output as
+//! `execute_call!(input, default = <output>)`, and the pallet exposes a
+//! `use_defaults()` function calling [`defaults::enable_for()`]:
+//!
+//! ```ignore
+//! #[mock_builder::test(runtime = Runtime)]
+//! fn transfer_failure() {
+//!     MockDep::use_defaults();
+//!     MockDep::mock_transfer(|_, _| Err(DispatchError::Other("no funds")));
+//!     // ...
+//! }
+//! ```
+//!
+//! See [`defaults`] for an example of *mock pallet*.
+//!
+//! ## Snapshots
+//!
+//! [`snapshot()`] captures the mocks registered so far, and [`restore()`]
+//! reinstates them in the current externalities, so each case of a
+//! table-driven test can start from the same baseline of mocks without
+//! registering it again. See [`snapshot`](mod@snapshot) for an example.
+//!
+//! ## Golden files
+//!
+//! The interactions with the mocked dependencies of a complex flow can be
+//! checked all at once against a golden file, given to the [`macro@test`]
+//! attribute as `golden = "<path>"`. Each mock execution is written with its
+//! method, its mock and the `Debug` representation of its input and output.
+//! A mismatch panics with a diff, and the file is rewritten when running the
+//! tests with `MOCK_BUILDER_BLESS=1`. See [`golden`] for an example.
+//!
+//! ## Property-based tests
+//!
+//! With the `proptest` or `quickcheck` features, a [`prop::MockOutputs`] can
+//! be generated as the sequence of outputs returned by a mock, so the pallets
+//! are tested against arbitrary behaviors of their dependencies. A failing
+//! case is shrunk to the minimal sequence of mock outputs. See [`prop`] for an
+//! example.
 //!
 //! ## Mock Patterns
 //!
@@ -234,6 +514,8 @@
 //! ```
 //!
 //! Any call to `get()` will return the last value given to `set()`.
+//! In [`strict`] mode, the inner registration must be wrapped by
+//! [`replace()`], since it replaces the previous `get()` mock.
 //!
 //! #### Check internal calls are ordered
 //! If you want to test some mocks method are calle in some order, you can
@@ -254,28 +536,188 @@
 //! MyPallet::calls_first_second_third();
 //! ```
 
+extern crate alloc;
+
 /// Provide functions for register/execute calls
+#[cfg(feature = "std")]
 pub mod storage;
 
 /// Provide functions for handle fuction locations
+#[cfg(feature = "std")]
 pub mod location;
 
-mod util;
+/// Provide the event deposited by mock pallets
+pub mod event;
+
+/// Provide the strict mode configuration
+#[cfg(feature = "std")]
+pub mod strict;
+
+/// Provide the default outputs of the mocks
+#[cfg(feature = "std")]
+pub mod defaults;
+
+/// Provide the trace events of registrations and executions
+#[cfg(feature = "std")]
+pub mod trace;
+
+/// Provide a parameter type whose value can be mocked
+pub mod get;
+
+/// Provide the default mocks to run benchmarks as tests
+pub mod benchmark;
+
+/// Provide the adapters to mock methods returning iterators
+pub mod iter;
+
+/// Provide the values returned by mocks for any type signature
+pub mod any;
+
+/// Provide the mocks for methods with a `self` receiver
+pub mod object;
+
+/// Provide the registry of mocks used without externalities
+#[cfg(feature = "std")]
+pub mod local;
+
+/// Provide the snapshots of the registered mocks
+#[cfg(feature = "std")]
+pub mod snapshot;
 
+/// Provide the golden files of the mock interactions
+#[cfg(feature = "std")]
+pub mod golden;
+
+/// Provide the adapters for property-based tests
+#[cfg(feature = "std")]
+pub mod prop;
+
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub mod util;
+
+/// Provide the stubs of the macros for builds without the `std` feature
+#[doc(hidden)]
+#[cfg(not(feature = "std"))]
+pub mod stub;
+
+/// Provide the dependencies used by the code expanded from the macros
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub mod __private {
+	pub use frame_system;
+	pub use sp_io;
+	pub use sp_runtime;
+}
+
+pub use benchmark::BenchmarkDefaults;
+pub use event::MockEvent;
+pub use get::MockGet;
+pub use iter::MockIter;
+#[cfg(feature = "std")]
+pub use local::LocalRegistry;
+#[cfg(feature = "std")]
 use location::{FunctionLocation, TraitInfo};
+/// Declare a runtime with `frame_system`, configured with its
+/// `TestDefaultConfig`, and the given pallets. An associated type bound with
+/// `=>` to a *mock pallet* adds it to the runtime, named as the type with a
+/// `Mock` prefix, and uses it as the type:
+///
+/// ```ignore
+/// mock_builder::mock_runtime! {
+///     pub struct Runtime;
+///
+///     System: frame_system {
+///         type AccountData = pallet_balances::AccountData<u64>;
+///     }
+///
+///     Balances: pallet_balances,
+///     Remarks: pallet_remarks {
+///         type MaxRemarksPerCall = ConstU32<3>;
+///         type Remark = TestRemark;
+///         type RemarkDispatchHandler => pallet_mock_remark_handler;
+///         type RuntimeCall = RuntimeCall;
+///         type RuntimeEvent = RuntimeEvent;
+///         type WeightInfo = ();
+///     }
+/// }
+///
+/// #[mock_builder::test(runtime = Runtime)]
+/// fn remark() {
+///     MockRemarkDispatchHandler::mock_pre_dispatch_check(|_| Ok(()));
+///     // ...
+/// }
+/// ```
+///
+/// The first pallet must be `frame_system`. The types of its optional block
+/// are added to its configuration, which already sets the `Block` type. The
+/// `Config` of a *mock pallet* accepts types the same way, i.e. `type Currency
+/// => pallet_mock_fungible { type Balance = u64; };`. A pallet without a
+/// block, as `Balances` above, must implement its `Config` out of the macro.
+///
+/// The macro doesn't generate the *mock pallets*, since it can't see the
+/// traits they mock. They're written as usual or generated by the
+/// `mock-builder` command line tool. Each of them can be bound to only one
+/// type, since its `Config` is implemented for the binding.
+pub use mock_builder_macros::mock_runtime;
+/// Run a test inside the externalities of a runtime, verifying and clearing
+/// the registered mocks at the end.
+///
+/// ```ignore
+/// #[mock_builder::test(runtime = Runtime)]
+/// fn my_test() {
+///     MockDep::mock_foo(|| true);
+///     MyPallet::my_call();
+/// }
+/// ```
+///
+/// The externalities are built with `frame_system::Pallet::externalities()`.
+/// It accepts the following optional arguments:
+/// - `genesis = <expr>`: build the externalities from a value implementing
+///   `sp_runtime::BuildStorage` instead, i.e. a `RuntimeGenesisConfig`. In this
+///   case, the test starts at block `1`.
+/// - `block = <expr>`: block number where the test starts.
+/// - `strict = <bool>`: enable the [`strict`] mode for all mock pallets.
+/// - `golden = <path>`: check the mock executions of the test against a
+///   [`golden`] file, relative to the package root.
+///
+/// Once the test body finishes, [`verify()`] reports the mocks never executed
+/// and [`clear()`] removes all of them. It adds the `#[test]` attribute, so
+/// there is no need to add it again.
+pub use mock_builder_macros::test;
+pub use object::MockObject;
+#[cfg(feature = "std")]
+pub use snapshot::{restore, snapshot, Snapshot};
+#[cfg(feature = "std")]
 pub use storage::CallId;
+#[cfg(feature = "std")]
+pub use strict::replace;
+#[cfg(not(feature = "std"))]
+pub use stub::{defaults, strict, CallId};
+#[cfg(feature = "std")]
+use util::DebugReprs;
 
 /// Prefix that the register functions should have.
 pub const MOCK_FN_PREFIX: &str = "mock_";
 
+/// Name of the dispatchable call exposed by mock pallets.
+pub const MOCK_DISPATCH_FN: &str = "mock_dispatch";
+
+/// Suffix that the register functions for any type signature should have.
+pub const ANY_FN_SUFFIX: &str = "_any";
+
 /// Register a mock function into the mock function storage.
 /// This function should be called with a locator used as a function
-/// identification.
-pub fn register<Locator, F, I, O, Insert>(locator: Locator, f: F, insert: Insert)
-where
+/// identification and the `Debug` representations of the function types.
+#[cfg(feature = "std")]
+pub fn register<Locator, F, I, O>(
+	locator: Locator,
+	f: F,
+	reprs: DebugReprs<I, O>,
+	insert: fn(String, CallId),
+) where
 	Locator: Fn(),
 	F: Fn(I) -> O + 'static,
-	Insert: Fn(String, CallId),
 {
 	let location = FunctionLocation::from(locator)
 		.normalize()
@@ -283,52 +725,468 @@ where
 		.assimilate_trait_prefix()
 		.append_type_signature::<I, O>();
 
-	insert(location.get(TraitInfo::Whatever), storage::register_call(f))
+	register_location(location, TraitInfo::Whatever, f, reprs, insert)
+}
+
+/// Register a mock function for any type signature of a generic method into
+/// the mock function storage. The function receives the name of the output
+/// type expected by each execution and returns a value of that type.
+/// This function should be called with a locator used as a function
+/// identification and the `Debug` representations of the function types.
+#[cfg(feature = "std")]
+pub fn register_any<Locator, F>(
+	locator: Locator,
+	f: F,
+	reprs: DebugReprs<&'static str, Box<dyn core::any::Any>>,
+	insert: fn(String, CallId),
+) where
+	Locator: Fn(),
+	F: Fn(&'static str) -> Box<dyn core::any::Any> + 'static,
+{
+	let location = FunctionLocation::from(locator)
+		.normalize()
+		.strip_name_prefix(MOCK_FN_PREFIX)
+		.strip_name_suffix(ANY_FN_SUFFIX)
+		.assimilate_trait_prefix()
+		.append_any_type_signature();
+
+	register_location(location, TraitInfo::Whatever, f, reprs, insert)
 }
 
 /// Execute a function from the function storage.
 /// This function should be called with a locator used as a function
 /// identification.
+#[cfg(feature = "std")]
 pub fn execute<Locator, I, O, Get>(locator: Locator, input: I, get: Get) -> O
 where
 	Locator: Fn(),
 	Get: Fn(String) -> Option<CallId>,
+{
+	let location = FunctionLocation::from(locator).normalize();
+
+	execute_location(location, input, get, None, None::<NoDefault<I, O>>)
+}
+
+/// Execute a function from the function storage or, if there is no mock for
+/// the types of the execution, the mock registered for any type with
+/// `register_any()`.
+/// This function should be called with a locator used as a function
+/// identification.
+#[cfg(feature = "std")]
+pub fn execute_any<Locator, I, O, Get>(locator: Locator, input: I, get: Get) -> O
+where
+	Locator: Fn(),
+	O: 'static,
+	Get: Fn(String) -> Option<CallId>,
+{
+	let location = FunctionLocation::from(locator).normalize();
+
+	execute_location(
+		location,
+		input,
+		get,
+		Some(any::downcast::<O>),
+		None::<NoDefault<I, O>>,
+	)
+}
+
+/// Execute a function from the function storage or, if no mock was
+/// registered for it and the [`defaults`] of its pallet are used, return the
+/// output of `default`.
+/// This function should be called with a locator used as a function
+/// identification and the `Debug` representations of the function types.
+#[cfg(feature = "std")]
+pub fn execute_or_default<Locator, I, O, Get, Default>(
+	locator: Locator,
+	input: I,
+	get: Get,
+	default: Default,
+	reprs: DebugReprs<I, O>,
+) -> O
+where
+	Locator: Fn(),
+	Get: Fn(String) -> Option<CallId>,
+	Default: FnOnce() -> O,
+{
+	let location = FunctionLocation::from(locator).normalize();
+	let default = defaults::is_enabled_at(&location).then_some((default, reprs));
+
+	execute_location(location, input, get, None, default)
+}
+
+/// Execute a function from the function storage, depositing a
+/// [`MockEvent::Called`] before.
+/// This function should be called with a locator used as a function
+/// identification. The input must be encodable, since its hash is part of the
+/// event.
+#[cfg(feature = "std")]
+pub fn execute_with_event<Locator, I, O, Get, Deposit>(
+	locator: Locator,
+	input: I,
+	get: Get,
+	deposit: Deposit,
+) -> O
+where
+	Locator: Fn(),
+	I: parity_scale_codec::Encode,
+	Get: Fn(String) -> Option<CallId>,
+	Deposit: Fn(MockEvent),
+{
+	let location = FunctionLocation::from(locator).normalize();
+
+	deposit(MockEvent::called(&location.method(), &input));
+
+	execute_location(location, input, get, None, None::<NoDefault<I, O>>)
+}
+
+/// Register the behavior of the `mock_dispatch` call for the given `id`.
+/// This function should be called with a locator used as a pallet
+/// identification, the name of the function calling it doesn't matter.
+#[cfg(feature = "std")]
+pub fn register_dispatch<Locator, F, I, O>(
+	locator: Locator,
+	id: u32,
+	f: F,
+	reprs: DebugReprs<I, O>,
+	insert: fn(String, CallId),
+) where
+	Locator: Fn(),
+	F: Fn(I) -> O + 'static,
 {
 	let location = FunctionLocation::from(locator)
 		.normalize()
+		.rename(MOCK_DISPATCH_FN)
+		.append_key(id)
 		.append_type_signature::<I, O>();
 
-	let call_id = get(location.get(TraitInfo::Yes))
-		.or_else(|| get(location.get(TraitInfo::No)))
-		.unwrap_or_else(|| panic!("Mock was not found. Location: {location:?}"));
+	register_location(location, TraitInfo::No, f, reprs, insert)
+}
+
+/// Execute the behavior of the `mock_dispatch` call for the given `id`.
+/// This function should be called with a locator used as a pallet
+/// identification.
+#[cfg(feature = "std")]
+pub fn execute_dispatch<Locator, I, O, Get>(locator: Locator, id: u32, input: I, get: Get) -> O
+where
+	Locator: Fn(),
+	Get: Fn(String) -> Option<CallId>,
+{
+	let location = FunctionLocation::from(locator)
+		.normalize()
+		.rename(MOCK_DISPATCH_FN)
+		.append_key(id);
 
-	storage::execute_call(call_id, input).unwrap_or_else(|err| {
-		panic!("{err}. Location: {location:?}");
-	})
+	execute_location(location, input, get, None, None::<NoDefault<I, O>>)
+}
+
+/// Locations of the mocks registered in the current thread that have never
+/// been executed nor replaced.
+#[cfg(feature = "std")]
+pub fn unused_mocks() -> Vec<String> {
+	storage::unused_calls()
+		.into_iter()
+		.map(|(_, location)| location)
+		.collect()
+}
+
+/// Locations of the mocks of the *mock pallet* `P` registered in the current
+/// thread that have not been replaced, i.e:
+/// `registered_mocks_for::<MockDep>()`. Each instance of a *mock pallet* has
+/// its own mocks.
+#[cfg(feature = "std")]
+pub fn registered_mocks_for<P>() -> Vec<String> {
+	let call_ids = snapshot::call_ids_at(std::any::type_name::<P>());
+	storage::active_calls()
+		.into_iter()
+		.filter(|(call_id, _)| call_ids.contains(call_id))
+		.map(|(_, location)| location)
+		.collect()
+}
+
+/// Locations of the mocks of the *mock pallet* `P` registered in the current
+/// thread that have never been executed nor replaced.
+/// Each instance of a *mock pallet* has its own mocks.
+#[cfg(feature = "std")]
+pub fn unused_mocks_for<P>() -> Vec<String> {
+	let call_ids = snapshot::call_ids_at(std::any::type_name::<P>());
+	storage::unused_calls()
+		.into_iter()
+		.filter(|(call_id, _)| call_ids.contains(call_id))
+		.map(|(_, location)| location)
+		.collect()
+}
+
+/// Check the mocks registered in the current thread, reporting the ones that
+/// have never been executed as [`trace`] events. It panics if any of them was
+/// registered in [`strict`] mode.
+#[cfg(feature = "std")]
+pub fn verify() {
+	let (strict, lax): (Vec<_>, Vec<_>) = storage::unused_calls()
+		.into_iter()
+		.partition(|(call_id, _)| strict::is_marked(*call_id));
+
+	for (_, location) in lax {
+		trace::unused(&location);
+	}
+
+	if !strict.is_empty() {
+		let locations = strict
+			.into_iter()
+			.map(|(_, location)| format!("- {location}"))
+			.collect::<Vec<_>>()
+			.join("\n");
+
+		panic!("Mocks registered but never executed in strict mode:\n{locations}");
+	}
+}
+
+/// Remove the mocks of the *mock pallet* `P` registered in the current thread
+/// and stop using its [`defaults`], i.e: `clear_for::<MockDep>()`. The mocks
+/// of other pallets, and other instances of the same pallet, are kept.
+/// Must not be called from a mock closure.
+#[cfg(feature = "std")]
+pub fn clear_for<P>() {
+	let pallet = std::any::type_name::<P>();
+	storage::remove_calls(&snapshot::call_ids_at(pallet));
+	snapshot::clear_at(pallet);
+	defaults::clear_for(pallet);
+}
+
+/// Remove all mocks registered in the current thread and disable the
+/// [`strict`] mode. Must not be called from a mock closure.
+#[cfg(feature = "std")]
+pub fn clear() {
+	storage::clear();
+	local::clear();
+	snapshot::clear();
+	golden::clear();
+	defaults::clear();
+	strict::reset();
+}
+
+#[cfg(feature = "std")]
+fn register_location<F, I, O>(
+	location: FunctionLocation,
+	trait_info: TraitInfo,
+	f: F,
+	reprs: DebugReprs<I, O>,
+	insert: fn(String, CallId),
+) where
+	F: Fn(I) -> O + 'static,
+{
+	let strict = strict::is_enabled_for(&location);
+	let location_repr = format!("{location:?}");
+
+	// The active mocks are only replaced once the registration is accepted.
+	if strict
+		&& !strict::is_replacing()
+		&& storage::active_calls_at(&location_repr)
+			.into_iter()
+			.any(strict::is_marked)
+	{
+		panic!(
+			"Mock already registered. Use `mock_builder::replace()` to replace it. Location: {location:?}"
+		);
+	}
+
+	storage::replace_calls_at(&location_repr);
+	let call_id = storage::register_call_at(location_repr, f, reprs);
+	if strict {
+		strict::mark(call_id);
+	}
+
+	snapshot::insert(location.get(trait_info), call_id, insert)
+}
+
+/// Mock found for the execution of a location.
+#[cfg(feature = "std")]
+enum Found {
+	/// Registered for the type signature of the execution.
+	Typed(FunctionLocation, CallId),
+
+	/// Registered for any type signature.
+	Any(FunctionLocation, CallId),
+}
+
+/// Find the mock of a location without type signature, registered for the
+/// types `I` and `O` or for any of them.
+#[cfg(feature = "std")]
+fn find_location<I, O, Get>(location: &FunctionLocation, get: &Get) -> Option<Found>
+where
+	Get: Fn(String) -> Option<CallId>,
+{
+	// A `CallId` can remain stored once its call is removed, i.e: by `clear_for()`.
+	let get = |location| get(location).filter(|call_id| storage::is_registered(*call_id));
+	let find = |location: &FunctionLocation| {
+		get(location.get(TraitInfo::Whatever)).or_else(|| get(location.get(TraitInfo::No)))
+	};
+
+	let typed = location.clone().append_type_signature::<I, O>();
+	if let Some(call_id) = find(&typed) {
+		return Some(Found::Typed(typed, call_id));
+	}
+
+	let any = location.clone().append_any_type_signature();
+	find(&any).map(|call_id| Found::Any(any, call_id))
+}
+
+/// Type of the default output of an execution without it.
+#[cfg(feature = "std")]
+type NoDefault<I, O> = (fn() -> O, DebugReprs<I, O>);
+
+/// Execute the mock of a location or, if there is none, the given default.
+/// The mocks registered for any type are only executed with `downcast`.
+#[cfg(feature = "std")]
+fn execute_location<I, O, Get, Default>(
+	location: FunctionLocation,
+	input: I,
+	get: Get,
+	downcast: Option<any::Downcast<O>>,
+	default: Option<(Default, DebugReprs<I, O>)>,
+) -> O
+where
+	Get: Fn(String) -> Option<CallId>,
+	Default: FnOnce() -> O,
+{
+	match find_location::<I, O, _>(&location, &get) {
+		Some(Found::Typed(typed, call_id)) => {
+			let execution = storage::execute_call_reported(call_id, input).unwrap_or_else(|err| {
+				panic!("{err}. Location: {typed:?}");
+			});
+
+			golden::record(&location, execution.input_repr, || {
+				(execution.output_repr)(&execution.output)
+			});
+
+			execution.output
+		}
+		Some(Found::Any(any, call_id)) => {
+			let expected = std::any::type_name::<O>();
+			let downcast = downcast.unwrap_or_else(|| {
+				panic!(
+					"Mock registered for any type, but the execution is not called as \
+					`execute_call!(input, any)`. Location: {any:?}"
+				)
+			});
+			let value = storage::execute_call(call_id, expected).unwrap_or_else(|err| {
+				panic!("{err}. Location: {any:?}");
+			});
+
+			// The closure doesn't receive the input and its output type is erased.
+			golden::record(&location, None, || None);
+
+			downcast(value).unwrap_or_else(|_| {
+				panic!(
+					"Mock returned a value of other type, expected {expected}. Location: {any:?}"
+				)
+			})
+		}
+		None => match default {
+			Some((default, reprs)) => {
+				let input_repr = (reprs.input)(&input);
+				let output = default();
+
+				golden::record(&location, input_repr, || (reprs.output)(&output));
+
+				output
+			}
+			None => panic!(
+				"Mock was not found. Location: {:?}",
+				location.append_type_signature::<I, O>()
+			),
+		},
+	}
 }
 
 /// Register a mock function into the mock function storage.
 /// Same as `register()` but it uses as locator who calls this macro.
+#[cfg(feature = "std")]
 #[macro_export]
 macro_rules! register_call {
 	($f:expr) => {{
-		$crate::register(|| (), $f, CallIds::<T>::insert);
+		let f = $f;
+		let reprs = $crate::debug_reprs!(&f);
+		$crate::register(|| (), f, reprs, CallIds::<T>::insert);
 	}};
 }
 
 /// Register a mock function into the mock function storage for a pallet with
 /// instances. Same as `register()` but it uses as locator who calls this macro.
+#[cfg(feature = "std")]
 #[macro_export]
 macro_rules! register_call_instance {
 	($f:expr) => {{
-		$crate::register(|| (), $f, CallIds::<T, I>::insert);
+		let f = $f;
+		let reprs = $crate::debug_reprs!(&f);
+		$crate::register(|| (), f, reprs, CallIds::<T, I>::insert);
+	}};
+}
+
+/// Register a mock function for any type signature of a generic method into
+/// the mock function storage.
+/// Same as `register_any()` but it uses as locator who calls this macro.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! register_call_any {
+	($f:expr) => {{
+		let f = $f;
+		let reprs = $crate::debug_reprs!(&f);
+		$crate::register_any(|| (), f, reprs, CallIds::<T>::insert);
+	}};
+}
+
+/// Register a mock function for any type signature of a generic method into
+/// the mock function storage for a pallet with instances.
+/// Same as `register_any()` but it uses as locator who calls this macro.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! register_call_instance_any {
+	($f:expr) => {{
+		let f = $f;
+		let reprs = $crate::debug_reprs!(&f);
+		$crate::register_any(|| (), f, reprs, CallIds::<T, I>::insert);
 	}};
 }
 
 /// Execute a function from the function storage.
 /// Same as `execute()` but it uses as locator who calls this macro.
+/// If called as `execute_call!(input, default = <output>)`, it's the same as
+/// `execute_or_default()`, returning `<output>` when there is no mock and the
+/// [`defaults`] of the pallet are used.
+/// If called as `execute_call!(input, any)`, it's the same as `execute_any()`.
+/// Without the `std` feature, it panics or returns `<output>` if called as
+/// `execute_call!(input, no_std = <output>)`.
+#[cfg(feature = "std")]
 #[macro_export]
 macro_rules! execute_call {
+	($input:expr, $(default = $default:expr,)? no_std = $output:expr) => {{
+		// The output is only used without the `std` feature
+		if false {
+			$output
+		} else {
+			$crate::execute_call!($input $(, default = $default)?)
+		}
+	}};
+	($input:expr, any, no_std = $output:expr) => {{
+		// The output is only used without the `std` feature
+		if false {
+			$output
+		} else {
+			$crate::execute_call!($input, any)
+		}
+	}};
+	($input:expr, any) => {{
+		$crate::execute_any(|| (), $input, CallIds::<T>::get)
+	}};
+	($input:expr, default = $default:expr) => {{
+		$crate::execute_or_default(
+			|| (),
+			$input,
+			CallIds::<T>::get,
+			|| $default,
+			$crate::debug_reprs!(),
+		)
+	}};
 	($input:expr) => {{
 		$crate::execute(|| (), $input, CallIds::<T>::get)
 	}};
@@ -336,9 +1194,334 @@ macro_rules! execute_call {
 
 /// Execute a function from the function storage for a pallet with instances.
 /// Same as `execute()` but it uses as locator who calls this macro.
+/// If called as `execute_call_instance!(input, default = <output>)`, it's the
+/// same as `execute_or_default()`, and if called as
+/// `execute_call_instance!(input, any)`, the same as `execute_any()`.
+/// Without the `std` feature, it panics or returns `<output>` if called as
+/// `execute_call_instance!(input, no_std = <output>)`.
+#[cfg(feature = "std")]
 #[macro_export]
 macro_rules! execute_call_instance {
+	($input:expr, $(default = $default:expr,)? no_std = $output:expr) => {{
+		// The output is only used without the `std` feature
+		if false {
+			$output
+		} else {
+			$crate::execute_call_instance!($input $(, default = $default)?)
+		}
+	}};
+	($input:expr, any, no_std = $output:expr) => {{
+		// The output is only used without the `std` feature
+		if false {
+			$output
+		} else {
+			$crate::execute_call_instance!($input, any)
+		}
+	}};
+	($input:expr, any) => {{
+		$crate::execute_any(|| (), $input, CallIds::<T, I>::get)
+	}};
+	($input:expr, default = $default:expr) => {{
+		$crate::execute_or_default(
+			|| (),
+			$input,
+			CallIds::<T, I>::get,
+			|| $default,
+			$crate::debug_reprs!(),
+		)
+	}};
 	($input:expr) => {{
 		$crate::execute(|| (), $input, CallIds::<T, I>::get)
 	}};
 }
+
+/// Execute a function from the function storage, depositing a
+/// [`MockEvent::Called`] as pallet event. The pallet `Event<T>` must be
+/// convertible from [`MockEvent`], and the input must implement `Encode`.
+/// Same as `execute_with_event()` but it uses as locator who calls this macro.
+/// Without the `std` feature, it panics or returns `<output>` if called as
+/// `execute_call_with_event!(input, no_std = <output>)`.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! execute_call_with_event {
+	($input:expr, no_std = $output:expr) => {{
+		// The output is only used without the `std` feature
+		if false {
+			$output
+		} else {
+			$crate::execute_call_with_event!($input)
+		}
+	}};
+	($input:expr) => {{
+		$crate::execute_with_event(
+			|| (),
+			$input,
+			CallIds::<T>::get,
+			|event| Pallet::<T>::deposit_event(event.into()),
+		)
+	}};
+}
+
+/// Execute a function from the function storage for a pallet with instances,
+/// depositing a [`MockEvent::Called`] as pallet event. The pallet
+/// `Event<T, I>` must be convertible from [`MockEvent`], and the input must
+/// implement `Encode`.
+/// Same as `execute_with_event()` but it uses as locator who calls this macro.
+/// Without the `std` feature, it panics or returns `<output>` if called as
+/// `execute_call_instance_with_event!(input, no_std = <output>)`.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! execute_call_instance_with_event {
+	($input:expr, no_std = $output:expr) => {{
+		// The output is only used without the `std` feature
+		if false {
+			$output
+		} else {
+			$crate::execute_call_instance_with_event!($input)
+		}
+	}};
+	($input:expr) => {{
+		$crate::execute_with_event(
+			|| (),
+			$input,
+			CallIds::<T, I>::get,
+			|event| Pallet::<T, I>::deposit_event(event.into()),
+		)
+	}};
+}
+
+/// Register the behavior of the `mock_dispatch` call for the given `id`.
+/// Same as `register_dispatch()` but it uses as locator who calls this macro.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! register_dispatch {
+	($id:expr, $f:expr) => {{
+		let f = $f;
+		let reprs = $crate::debug_reprs!(&f);
+		$crate::register_dispatch(|| (), $id, f, reprs, CallIds::<T>::insert);
+	}};
+}
+
+/// Register the behavior of the `mock_dispatch` call for the given `id` for a
+/// pallet with instances. Same as `register_dispatch()` but it uses as locator
+/// who calls this macro.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! register_dispatch_instance {
+	($id:expr, $f:expr) => {{
+		let f = $f;
+		let reprs = $crate::debug_reprs!(&f);
+		$crate::register_dispatch(|| (), $id, f, reprs, CallIds::<T, I>::insert);
+	}};
+}
+
+/// Execute the behavior of the `mock_dispatch` call for the given `id`.
+/// Same as `execute_dispatch()` but it uses as locator who calls this macro.
+/// Without the `std` feature, it panics or returns `<output>` if called as
+/// `execute_dispatch!(id, origin, no_std = <output>)`.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! execute_dispatch {
+	($id:expr, $origin:expr, no_std = $output:expr) => {{
+		// The output is only used without the `std` feature
+		if false {
+			$output
+		} else {
+			$crate::execute_dispatch!($id, $origin)
+		}
+	}};
+	($id:expr, $origin:expr) => {{
+		$crate::execute_dispatch(|| (), $id, $origin, CallIds::<T>::get)
+	}};
+}
+
+/// Execute the behavior of the `mock_dispatch` call for the given `id` for a
+/// pallet with instances. Same as `execute_dispatch()` but it uses as locator
+/// who calls this macro.
+/// Without the `std` feature, it panics or returns `<output>` if called as
+/// `execute_dispatch_instance!(id, origin, no_std = <output>)`.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! execute_dispatch_instance {
+	($id:expr, $origin:expr, no_std = $output:expr) => {{
+		// The output is only used without the `std` feature
+		if false {
+			$output
+		} else {
+			$crate::execute_dispatch_instance!($id, $origin)
+		}
+	}};
+	($id:expr, $origin:expr) => {{
+		$crate::execute_dispatch(|| (), $id, $origin, CallIds::<T, I>::get)
+	}};
+}
+
+/// Register a mock function into the mock function storage of the current
+/// thread, without externalities nor a pallet.
+/// Same as `register()` but it uses as locator who calls this macro.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! register_call_local {
+	($f:expr) => {{
+		let f = $f;
+		let reprs = $crate::debug_reprs!(&f);
+		$crate::register(|| (), f, reprs, $crate::LocalRegistry::insert);
+	}};
+}
+
+/// Execute a function from the mock function storage of the current thread,
+/// without externalities nor a pallet.
+/// Same as `execute()` but it uses as locator who calls this macro.
+/// If called as `execute_call_local!(input, default = <output>)`, it's the
+/// same as `execute_or_default()`.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! execute_call_local {
+	($input:expr, default = $default:expr) => {{
+		$crate::execute_or_default(
+			|| (),
+			$input,
+			$crate::LocalRegistry::get,
+			|| $default,
+			$crate::debug_reprs!(),
+		)
+	}};
+	($input:expr) => {{
+		$crate::execute(|| (), $input, $crate::LocalRegistry::get)
+	}};
+}
+
+/// Register a mock function of a mock struct, for all its instances or for
+/// the one given to [`object::for_instance()`].
+/// Same as `object::register()` but it uses as locator who calls this macro.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! register_object_call {
+	($f:expr) => {{
+		let f = $f;
+		let reprs = $crate::debug_reprs!(&f);
+		$crate::object::register(|| (), f, reprs);
+	}};
+}
+
+/// Execute a mock function of a mock struct for the given instance.
+/// Same as `object::execute()` but it uses as locator who calls this macro.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! execute_object_call {
+	($object:expr, $input:expr) => {{
+		$crate::object::execute(|| (), $object, $input)
+	}};
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+	use std::marker::PhantomData;
+
+	use super::{clear_for, defaults, registered_mocks_for, unused_mocks, unused_mocks_for};
+
+	trait Oracle {
+		fn price(asset: u32) -> u64;
+	}
+
+	struct Instance1;
+	struct Instance2;
+
+	struct MockOracle<I = ()>(PhantomData<I>);
+
+	impl<I> MockOracle<I> {
+		fn use_defaults() {
+			defaults::enable_for::<Self>();
+		}
+
+		fn mock_price(f: impl Fn(u32) -> u64 + 'static) {
+			crate::register_call_local!(f);
+		}
+	}
+
+	impl<I> Oracle for MockOracle<I> {
+		fn price(asset: u32) -> u64 {
+			crate::execute_call_local!(asset, default = 0)
+		}
+	}
+
+	type MockOracle1 = MockOracle<Instance1>;
+	type MockOracle2 = MockOracle<Instance2>;
+
+	#[test]
+	fn instances_with_own_mocks() {
+		MockOracle1::mock_price(|_| 1);
+		MockOracle2::mock_price(|_| 2);
+
+		assert_eq!(MockOracle1::price(0), 1);
+		assert_eq!(MockOracle2::price(0), 2);
+	}
+
+	#[test]
+	#[should_panic(expected = "Mock was not found")]
+	fn not_leaked_to_other_instance() {
+		MockOracle1::mock_price(|_| 1);
+
+		MockOracle2::price(0);
+	}
+
+	#[test]
+	fn listed_by_instance() {
+		MockOracle1::mock_price(|_| 1);
+		MockOracle2::mock_price(|_| 2);
+		MockOracle2::price(0);
+
+		let registered = registered_mocks_for::<MockOracle1>();
+		assert_eq!(registered.len(), 1);
+		assert!(registered[0].contains("MockOracle<mock_builder::tests::Instance1>::price"));
+
+		assert_eq!(unused_mocks_for::<MockOracle1>(), registered);
+		assert_eq!(registered_mocks_for::<MockOracle2>().len(), 1);
+		assert!(unused_mocks_for::<MockOracle2>().is_empty());
+		assert!(registered_mocks_for::<MockOracle>().is_empty());
+	}
+
+	#[test]
+	fn cleared_by_instance() {
+		MockOracle1::use_defaults();
+		MockOracle1::mock_price(|_| 1);
+		MockOracle2::mock_price(|_| 2);
+
+		clear_for::<MockOracle1>();
+
+		assert!(registered_mocks_for::<MockOracle1>().is_empty());
+		assert!(!defaults::is_enabled_for::<MockOracle1>());
+		assert_eq!(MockOracle2::price(0), 2);
+		assert_eq!(unused_mocks(), Vec::<String>::new());
+	}
+
+	#[test]
+	#[should_panic(expected = "Mock was not found")]
+	fn not_found_once_cleared() {
+		MockOracle1::mock_price(|_| 1);
+		clear_for::<MockOracle1>();
+
+		MockOracle1::price(0);
+	}
+
+	#[test]
+	fn default_once_cleared() {
+		MockOracle1::mock_price(|_| 1);
+		clear_for::<MockOracle1>();
+		MockOracle1::use_defaults();
+
+		assert_eq!(MockOracle1::price(0), 0);
+	}
+
+	#[test]
+	fn kept_when_overwritten_in_strict_mode() {
+		crate::strict::enable();
+		MockOracle1::mock_price(|_| 1);
+
+		let overwritten = std::panic::catch_unwind(|| MockOracle1::mock_price(|_| 2));
+
+		assert!(overwritten.is_err());
+		assert_eq!(registered_mocks_for::<MockOracle1>().len(), 1);
+		assert_eq!(MockOracle1::price(0), 1);
+	}
+}
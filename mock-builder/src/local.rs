@@ -0,0 +1,101 @@
+//! Mocks registered in the current thread instead of the storage of a mock
+//! pallet, so they work in ordinary tests without externalities. Any struct
+//! can be a mock, not only a pallet:
+//!
+//! ```ignore
+//! pub struct MockOracle;
+//!
+//! impl MockOracle {
+//!     pub fn mock_price(f: impl Fn(AssetId) -> Balance + 'static) {
+//!         register_call_local!(f);
+//!     }
+//! }
+//!
+//! impl Oracle for MockOracle {
+//!     fn price(asset: AssetId) -> Balance {
+//!         execute_call_local!(asset)
+//!     }
+//! }
+//!
+//! #[test]
+//! fn quote() {
+//!     MockOracle::mock_price(|_| 42);
+//!     assert_eq!(quote::<MockOracle>(ASSET, 2), 84);
+//! }
+//! ```
+
+use std::{cell::RefCell, collections::HashMap};
+
+use super::CallId;
+
+thread_local! {
+	static CALL_IDS: RefCell<HashMap<String, CallId>> = RefCell::new(HashMap::default());
+}
+
+/// Call identifications of the mocks registered in the current thread, used
+/// instead of the `CallIds` storage of a mock pallet.
+pub struct LocalRegistry;
+
+impl LocalRegistry {
+	/// Store the call identification of a location.
+	pub fn insert(location: String, call_id: CallId) {
+		CALL_IDS.with(|ids| ids.borrow_mut().insert(location, call_id));
+	}
+
+	/// Call identification of a location.
+	pub fn get(location: String) -> Option<CallId> {
+		CALL_IDS.with(|ids| ids.borrow().get(&location).copied())
+	}
+}
+
+/// Forget the call identifications of the current thread.
+pub(crate) fn clear() {
+	CALL_IDS.with(|ids| ids.borrow_mut().clear());
+}
+
+#[cfg(test)]
+mod tests {
+	trait Oracle {
+		fn price(asset: u32) -> u64;
+		fn quote(asset: u32, amount: u64) -> u64;
+	}
+
+	struct MockOracle;
+
+	impl MockOracle {
+		fn mock_price(f: impl Fn(u32) -> u64 + 'static) {
+			crate::register_call_local!(f);
+		}
+
+		fn mock_quote(f: impl Fn(u32, u64) -> u64 + 'static) {
+			crate::register_call_local!(move |(a, b)| f(a, b));
+		}
+	}
+
+	impl Oracle for MockOracle {
+		fn price(asset: u32) -> u64 {
+			crate::execute_call_local!(asset)
+		}
+
+		fn quote(asset: u32, amount: u64) -> u64 {
+			crate::execute_call_local!((asset, amount))
+		}
+	}
+
+	#[test]
+	fn without_externalities() {
+		MockOracle::mock_price(|asset| asset as u64 * 10);
+		MockOracle::mock_quote(|asset, amount| MockOracle::price(asset) * amount);
+
+		assert_eq!(MockOracle::quote(2, 3), 60);
+	}
+
+	#[test]
+	#[should_panic(expected = "Mock was not found")]
+	fn cleared() {
+		MockOracle::mock_price(|_| 1);
+
+		crate::clear();
+		MockOracle::price(1);
+	}
+}
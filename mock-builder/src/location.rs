@@ -26,8 +26,13 @@ impl FunctionLocation {
 	/// Creates a location for the function which created the given closure used
 	/// as a locator
 	pub fn from<F: Fn()>(_: F) -> Self {
-		let location = std::any::type_name::<F>();
-		let location = &location[..location.len() - "::{{closure}}".len()];
+		let mut location = std::any::type_name::<F>();
+
+		// The locator can be nested in other closures, i.e. when the function body is
+		// wrapped by a macro. We only want the function that contains them.
+		while let Some(parent) = location.strip_suffix("::{{closure}}") {
+			location = parent;
+		}
 
 		// Remove generic attributes from signature if it has any
 		let location = location
@@ -99,6 +104,22 @@ impl FunctionLocation {
 		}
 	}
 
+	/// Remove the suffix from the function name.
+	pub fn strip_name_suffix(self, suffix: &str) -> Self {
+		let (path, name) = self.location.rsplit_once("::").expect("always ::");
+		let name = name.strip_suffix(suffix).unwrap_or_else(|| {
+			panic!(
+				"Function '{name}' should have a '{suffix}' suffix. Location: {}",
+				self.location
+			)
+		});
+
+		Self {
+			location: format!("{path}::{name}"),
+			trait_info: self.trait_info,
+		}
+	}
+
 	/// Remove the trait name from the function name and add such information to
 	/// the location. The location is expected to have the following structure:
 	/// `<path>::<TraitInfo>_<name>`
@@ -118,6 +139,25 @@ impl FunctionLocation {
 		}
 	}
 
+	/// Replace the function name of the location.
+	pub fn rename(self, name: &str) -> Self {
+		let (path, _) = self.location.rsplit_once("::").expect("always ::");
+
+		Self {
+			location: format!("{path}::{name}"),
+			trait_info: self.trait_info,
+		}
+	}
+
+	/// Add a key to the function name, allowing to identify several locations
+	/// for the same function.
+	pub fn append_key(self, key: impl std::fmt::Display) -> Self {
+		Self {
+			location: format!("{}#{}", self.location, key),
+			trait_info: self.trait_info,
+		}
+	}
+
 	/// Add a representation of the function input and output types
 	pub fn append_type_signature<I, O>(self) -> Self {
 		Self {
@@ -126,6 +166,50 @@ impl FunctionLocation {
 		}
 	}
 
+	/// Add a representation of any function input and output types
+	pub fn append_any_type_signature(self) -> Self {
+		Self {
+			location: format!("{}:*", self.location),
+			trait_info: self.trait_info,
+		}
+	}
+
+	/// Name of the function, prefixed by the trait name if it has trait info.
+	/// Must be called before appending the type signature.
+	pub fn method(&self) -> String {
+		let (_, name) = self.location.rsplit_once("::").expect("always ::");
+		match &self.trait_info {
+			Some(trait_info) => format!("{trait_info}::{name}"),
+			None => name.into(),
+		}
+	}
+
+	/// Path where the function is defined, i.e: a pallet path.
+	pub fn path(&self) -> &str {
+		let (path, _) = self.location.rsplit_once("::").expect("always ::");
+		path
+	}
+
+	/// Name of the type where the function is defined, without its module path
+	/// nor generics. For a *mock pallet*, whose type is always `Pallet`, it's
+	/// the name of its module.
+	pub fn mock_name(&self) -> &str {
+		let path = self.path();
+		let path = path.split_once('<').map_or(path, |(path, _generics)| path);
+		match path.rsplit_once("::") {
+			Some((module, "Pallet")) => module.rsplit_once("::").map_or(module, |(_, name)| name),
+			Some((_, name)) => name,
+			None => path,
+		}
+	}
+
+	/// Check if the function belongs to the given path, i.e: a pallet path.
+	pub fn belongs_to(&self, path: &str) -> bool {
+		self.location
+			.strip_prefix(path)
+			.map_or(false, |rest| rest.starts_with("::"))
+	}
+
 	/// Generate a hash of the location
 	pub fn get(&self, trait_info: TraitInfo) -> String {
 		let trait_info = match trait_info {
@@ -174,9 +258,14 @@ mod tests {
 			FunctionLocation::from(|| ())
 		}
 
+		#[allow(clippy::extra_unused_type_parameters)]
 		fn mock_generic_method<A: Into<i32>>(_: impl Into<u32>) -> FunctionLocation {
 			FunctionLocation::from(|| ())
 		}
+
+		fn mock_nested() -> FunctionLocation {
+			wrap(|| FunctionLocation::from(|| ()))
+		}
 	}
 
 	impl<T> TraitExample for Example<T> {
@@ -195,6 +284,22 @@ mod tests {
 		}
 	}
 
+	mod pallet_mock_example {
+		use super::*;
+
+		pub struct Pallet<T>(core::marker::PhantomData<T>);
+
+		impl<T> Pallet<T> {
+			pub fn mock_method() -> FunctionLocation {
+				FunctionLocation::from(|| ())
+			}
+		}
+	}
+
+	fn wrap<R>(f: impl FnOnce() -> R) -> R {
+		f()
+	}
+
 	struct TestConfig;
 	impl Config for TestConfig {
 		type Assoc = u32;
@@ -257,6 +362,17 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn function_location_from_nested_closure() {
+		assert_eq!(
+			Example::<TestConfig>::mock_nested(),
+			FunctionLocation {
+				location: format!("{PREFIX}::Example<{PREFIX}::TestConfig>::mock_nested"),
+				trait_info: None,
+			}
+		);
+	}
+
 	#[test]
 	fn normalized() {
 		assert_eq!(
@@ -305,6 +421,17 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn striped_name_suffix() {
+		assert_eq!(
+			Example::<TestConfig>::mock_method().strip_name_suffix("_method"),
+			FunctionLocation {
+				location: format!("{PREFIX}::Example<{PREFIX}::TestConfig>::mock"),
+				trait_info: None,
+			}
+		);
+	}
+
 	#[test]
 	fn assimilated_trait_prefix() {
 		assert_eq!(
@@ -338,6 +465,66 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn method() {
+		assert_eq!(Example::<TestConfig>::mock_method().method(), "mock_method");
+		assert_eq!(
+			Example::<TestConfig>::method().normalize().method(),
+			"TraitExample::method"
+		);
+	}
+
+	#[test]
+	fn path() {
+		assert_eq!(
+			Example::<TestConfig>::method().normalize().path(),
+			format!("{PREFIX}::Example<{PREFIX}::TestConfig>")
+		);
+	}
+
+	#[test]
+	fn mock_name() {
+		assert_eq!(
+			Example::<TestConfig>::method().normalize().mock_name(),
+			"Example"
+		);
+		assert_eq!(
+			pallet_mock_example::Pallet::<TestConfig>::mock_method().mock_name(),
+			"pallet_mock_example"
+		);
+	}
+
+	#[test]
+	fn belongs_to() {
+		let location = Example::<TestConfig>::mock_method();
+
+		assert!(location.belongs_to(&format!("{PREFIX}::Example<{PREFIX}::TestConfig>")));
+		assert!(!location.belongs_to(&format!("{PREFIX}::Example")));
+		assert!(!location.belongs_to(&format!("{PREFIX}::Other")));
+	}
+
+	#[test]
+	fn renamed() {
+		assert_eq!(
+			Example::<TestConfig>::mock_method().rename("dispatch"),
+			FunctionLocation {
+				location: format!("{PREFIX}::Example<{PREFIX}::TestConfig>::dispatch"),
+				trait_info: None,
+			}
+		);
+	}
+
+	#[test]
+	fn appended_key() {
+		assert_eq!(
+			Example::<TestConfig>::mock_method().append_key(42),
+			FunctionLocation {
+				location: format!("{PREFIX}::Example<{PREFIX}::TestConfig>::mock_method#42"),
+				trait_info: None,
+			}
+		);
+	}
+
 	#[test]
 	fn appended_type_signature() {
 		assert_eq!(
@@ -348,4 +535,14 @@ mod tests {
 			}
 		);
 	}
+	#[test]
+	fn appended_any_type_signature() {
+		assert_eq!(
+			Example::<TestConfig>::mock_method().append_any_type_signature(),
+			FunctionLocation {
+				location: format!("{PREFIX}::Example<{PREFIX}::TestConfig>::mock_method:*"),
+				trait_info: None,
+			}
+		);
+	}
 }
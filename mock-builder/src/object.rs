@@ -0,0 +1,228 @@
+//! Mocks for methods with a `self` receiver, implemented by plain structs
+//! whose instances carry an id, i.e. handler objects, `Imbalance`-like values
+//! or `Box<dyn Trait>` strategies. They don't need a pallet nor its storage:
+//!
+//! ```ignore
+//! mock_builder::mock_object!(pub struct MockHandler);
+//!
+//! impl MockHandler {
+//!     pub fn mock_handle(f: impl Fn(&Self, u32) -> bool + 'static) {
+//!         register_object_call!(move |(a, b)| f(a, b));
+//!     }
+//! }
+//!
+//! impl Handler for MockHandler {
+//!     fn handle(&self, n: u32) -> bool {
+//!         execute_object_call!(self, (self, n))
+//!     }
+//! }
+//! ```
+//!
+//! A mock is registered for all the instances of the struct, unless it's
+//! registered inside [`for_instance()`](crate::object::for_instance). The mock
+//! of an instance takes precedence:
+//!
+//! ```ignore
+//! MockHandler::mock_handle(|_, n| n > 0);
+//! mock_builder::object::for_instance(&MockHandler(1), || {
+//!     MockHandler::mock_handle(|_, _| false)
+//! });
+//!
+//! assert!(MockHandler(0).handle(1));
+//! assert!(!MockHandler(1).handle(1));
+//! ```
+
+#[cfg(feature = "std")]
+use std::cell::Cell;
+
+#[cfg(feature = "std")]
+use super::{
+	local::LocalRegistry,
+	location::{FunctionLocation, TraitInfo},
+	util::DebugReprs,
+	MOCK_FN_PREFIX,
+};
+
+#[cfg(feature = "std")]
+thread_local! {
+	static INSTANCE: Cell<Option<u32>> = const { Cell::new(None) };
+}
+
+/// Mock struct whose instances are identified by an id.
+pub trait MockObject {
+	/// Identification of the instance.
+	fn mock_id(&self) -> u32;
+}
+
+/// Declare a mock struct whose instances are identified by their `u32`.
+///
+/// ```ignore
+/// mock_builder::mock_object!(
+///     /// Mock for the `Handler` trait
+///     pub struct MockHandler;
+/// );
+/// ```
+#[macro_export]
+macro_rules! mock_object {
+	($(#[$attr:meta])* $vis:vis struct $name:ident $(;)?) => {
+		$(#[$attr])*
+		#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+		$vis struct $name(pub u32);
+
+		impl $crate::object::MockObject for $name {
+			fn mock_id(&self) -> u32 {
+				self.0
+			}
+		}
+	};
+}
+
+/// Register the mocks inside `f` only for the given instance, instead of all
+/// the instances of its struct.
+#[cfg(feature = "std")]
+pub fn for_instance<R>(object: &impl MockObject, f: impl FnOnce() -> R) -> R {
+	let _previous = Previous(INSTANCE.with(|instance| instance.replace(Some(object.mock_id()))));
+	f()
+}
+
+/// Instance of the registrations, restored when dropped, even if a mock
+/// panics.
+#[cfg(feature = "std")]
+struct Previous(Option<u32>);
+
+#[cfg(feature = "std")]
+impl Drop for Previous {
+	fn drop(&mut self) {
+		INSTANCE.with(|instance| instance.set(self.0));
+	}
+}
+
+/// Register a mock function of a mock struct.
+/// This function should be called with a locator used as a function
+/// identification and the `Debug` representations of the function types.
+#[cfg(feature = "std")]
+pub fn register<Locator, F, I, O>(locator: Locator, f: F, reprs: DebugReprs<I, O>)
+where
+	Locator: Fn(),
+	F: Fn(I) -> O + 'static,
+{
+	let location = FunctionLocation::from(locator)
+		.normalize()
+		.strip_name_prefix(MOCK_FN_PREFIX)
+		.assimilate_trait_prefix();
+
+	let location = match INSTANCE.with(Cell::get) {
+		Some(id) => location.append_key(id),
+		None => location,
+	};
+
+	super::register_location(
+		location.append_type_signature::<I, O>(),
+		TraitInfo::Whatever,
+		f,
+		reprs,
+		LocalRegistry::insert,
+	)
+}
+
+/// Execute a mock function of a mock struct for the given instance, or for
+/// all its instances if there is no mock for that one.
+/// This function should be called with a locator used as a function
+/// identification.
+#[cfg(feature = "std")]
+pub fn execute<Locator, I, O>(locator: Locator, object: &impl MockObject, input: I) -> O
+where
+	Locator: Fn(),
+{
+	let location = FunctionLocation::from(locator).normalize();
+	let instance_location = location.clone().append_key(object.mock_id());
+
+	let location = match super::find_location::<I, O, _>(&instance_location, &LocalRegistry::get) {
+		Some(_) => instance_location,
+		None => location,
+	};
+
+	super::execute_location(
+		location,
+		input,
+		LocalRegistry::get,
+		None,
+		None::<super::NoDefault<I, O>>,
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	trait Handler {
+		fn handle(&self, n: u32) -> bool;
+	}
+
+	crate::mock_object!(
+		struct MockHandler;
+	);
+
+	impl MockHandler {
+		fn mock_handle(f: impl Fn(&Self, u32) -> bool + 'static) {
+			crate::register_object_call!(move |(a, b)| f(a, b));
+		}
+	}
+
+	impl Handler for MockHandler {
+		fn handle(&self, n: u32) -> bool {
+			crate::execute_object_call!(self, (self, n))
+		}
+	}
+
+	#[test]
+	fn all_instances() {
+		MockHandler::mock_handle(|handler, n| handler.0 == n);
+
+		assert!(MockHandler(1).handle(1));
+		assert!(!MockHandler(2).handle(1));
+	}
+
+	#[test]
+	fn one_instance() {
+		MockHandler::mock_handle(|_, _| true);
+		for_instance(&MockHandler(1), || MockHandler::mock_handle(|_, _| false));
+
+		assert!(MockHandler(0).handle(1));
+		assert!(!MockHandler(1).handle(1));
+	}
+
+	#[test]
+	#[should_panic(expected = "Mock was not found")]
+	fn other_instance_not_found() {
+		for_instance(&MockHandler(1), || MockHandler::mock_handle(|_, _| false));
+
+		MockHandler(0).handle(1);
+	}
+
+	#[test]
+	fn restored_on_panic() {
+		let panicked =
+			std::panic::catch_unwind(|| for_instance(&MockHandler(1), || panic!("mock panicked")));
+
+		assert!(panicked.is_err());
+		assert_eq!(INSTANCE.with(Cell::get), None);
+	}
+
+	#[test]
+	fn boxed_object() {
+		MockHandler::mock_handle(|_, n| n > 0);
+
+		let handler: Box<dyn Handler> = Box::new(MockHandler(0));
+		assert!(handler.handle(1));
+	}
+
+	#[test]
+	#[should_panic(expected = "Mock was not found")]
+	fn cleared() {
+		MockHandler::mock_handle(|_, _| true);
+
+		crate::clear();
+		MockHandler(0).handle(1);
+	}
+}
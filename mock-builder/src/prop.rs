@@ -0,0 +1,213 @@
+//! Adapters to run property-based tests against arbitrary behaviors of the
+//! mocked dependencies. A [`MockOutputs`](crate::prop::MockOutputs) is a
+//! generated sequence of outputs returned by the consecutive executions of a
+//! mock:
+//!
+//! ```ignore
+//! proptest! {
+//!     #[test]
+//!     fn remark(checks in any::<MockOutputs<bool>>(), remarks in any::<Vec<u32>>()) {
+//!         System::externalities().execute_with(|| {
+//!             MockHandler::mock_pre_dispatch_check(move |_| match checks.next() {
+//!                 true => Ok(()),
+//!                 false => Err(DispatchError::Other("rejected")),
+//!             });
+//!             // ...
+//!         });
+//!         mock_builder::clear();
+//!     }
+//! }
+//! ```
+//!
+//! The sequences shrink as any other generated value, so a failing case is
+//! reported with the minimal sequence of mock outputs along with the test
+//! input. [`proptest`](https://docs.rs/proptest) strategies are provided with
+//! the `proptest` feature, and the
+//! [`quickcheck`](https://docs.rs/quickcheck) `Arbitrary` implementation with
+//! the `quickcheck` feature.
+
+use std::{cell::Cell, fmt};
+
+/// Maximum length of the arbitrary sequences of outputs.
+pub const MAX_OUTPUTS: usize = 16;
+
+/// Sequence of outputs returned by the consecutive executions of a mock,
+/// starting again from the first one once exhausted.
+#[derive(Clone)]
+pub struct MockOutputs<O> {
+	outputs: Vec<O>,
+	next: Cell<usize>,
+}
+
+impl<O> MockOutputs<O> {
+	/// Create a sequence with the given outputs. Panics if there are none.
+	pub fn new(outputs: Vec<O>) -> Self {
+		assert!(!outputs.is_empty(), "Mock outputs can not be empty");
+
+		Self {
+			outputs,
+			next: Cell::new(0),
+		}
+	}
+
+	/// Outputs of the sequence.
+	pub fn outputs(&self) -> &[O] {
+		&self.outputs
+	}
+}
+
+impl<O: Clone> MockOutputs<O> {
+	/// Output for the next execution of the mock.
+	pub fn next(&self) -> O {
+		let index = self
+			.next
+			.replace((self.next.get() + 1) % self.outputs.len());
+		self.outputs[index].clone()
+	}
+}
+
+impl<O: fmt::Debug> fmt::Debug for MockOutputs<O> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list().entries(&self.outputs).finish()
+	}
+}
+
+/// Strategy generating sequences of outputs with the given strategy, whose
+/// length is in the given range. Empty sequences are discarded.
+#[cfg(feature = "proptest")]
+pub fn outputs<S>(
+	output: S,
+	size: impl Into<proptest::collection::SizeRange>,
+) -> impl proptest::strategy::Strategy<Value = MockOutputs<S::Value>>
+where
+	S: proptest::strategy::Strategy,
+{
+	use proptest::strategy::Strategy;
+
+	proptest::collection::vec(output, size)
+		.prop_filter("Mock outputs can not be empty", |outputs| {
+			!outputs.is_empty()
+		})
+		.prop_map(MockOutputs::new)
+}
+
+#[cfg(feature = "proptest")]
+impl<O> proptest::arbitrary::Arbitrary for MockOutputs<O>
+where
+	O: proptest::arbitrary::Arbitrary + 'static,
+{
+	type Parameters = O::Parameters;
+	type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+	fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+		use proptest::strategy::Strategy;
+
+		outputs(proptest::arbitrary::any_with::<O>(args), 1..=MAX_OUTPUTS).boxed()
+	}
+}
+
+#[cfg(feature = "quickcheck")]
+impl<O: quickcheck::Arbitrary> quickcheck::Arbitrary for MockOutputs<O> {
+	fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+		let len = usize::arbitrary(g) % MAX_OUTPUTS + 1;
+		Self::new((0..len).map(|_| O::arbitrary(g)).collect())
+	}
+
+	fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+		Box::new(
+			self.outputs
+				.shrink()
+				.filter(|outputs| !outputs.is_empty())
+				.map(Self::new),
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn repeated_once_exhausted() {
+		let outputs = MockOutputs::new(vec![1, 2]);
+
+		assert_eq!(
+			(0..5).map(|_| outputs.next()).collect::<Vec<_>>(),
+			vec![1, 2, 1, 2, 1]
+		);
+	}
+
+	#[test]
+	fn debug_repr() {
+		let outputs = MockOutputs::new(vec![true, false]);
+		outputs.next();
+
+		assert_eq!(format!("{outputs:?}"), "[true, false]");
+	}
+
+	#[test]
+	#[should_panic(expected = "Mock outputs can not be empty")]
+	fn empty() {
+		MockOutputs::<u8>::new(vec![]);
+	}
+
+	#[cfg(feature = "proptest")]
+	mod proptest_adapter {
+		use proptest::prelude::*;
+
+		use super::*;
+
+		proptest! {
+			#[test]
+			fn generated(outputs in super::super::outputs(any::<u8>(), 0..4)) {
+				prop_assert!((1..4).contains(&outputs.outputs().len()));
+			}
+
+			#[test]
+			fn arbitrary(outputs in any::<MockOutputs<bool>>()) {
+				prop_assert!((1..=MAX_OUTPUTS).contains(&outputs.outputs().len()));
+			}
+		}
+
+		#[test]
+		fn shrunk_to_minimal_outputs() {
+			let mut runner = proptest::test_runner::TestRunner::deterministic();
+			let result = runner.run(&any::<MockOutputs<bool>>(), |outputs| {
+				prop_assert!(!outputs.outputs().contains(&true));
+				Ok(())
+			});
+
+			match result {
+				Err(proptest::test_runner::TestError::Fail(_, outputs)) => {
+					assert_eq!(outputs.outputs(), [true])
+				}
+				_ => panic!("expected a failure"),
+			}
+		}
+	}
+
+	#[cfg(feature = "quickcheck")]
+	mod quickcheck_adapter {
+		use quickcheck::Arbitrary;
+
+		use super::*;
+
+		#[test]
+		fn arbitrary() {
+			let mut g = quickcheck::Gen::new(10);
+			for _ in 0..100 {
+				let outputs = MockOutputs::<u8>::arbitrary(&mut g);
+				assert!((1..=MAX_OUTPUTS).contains(&outputs.outputs().len()));
+			}
+		}
+
+		#[test]
+		fn shrunk_not_empty() {
+			let outputs = MockOutputs::new(vec![3u8, 5]);
+
+			assert!(outputs
+				.shrink()
+				.all(|outputs| !outputs.outputs().is_empty()));
+		}
+	}
+}
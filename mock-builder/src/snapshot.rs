@@ -0,0 +1,183 @@
+//! Snapshots of the mocks registered in the current thread, so table-driven
+//! tests can register a baseline of mocks once and start each case from it:
+//!
+//! ```ignore
+//! let baseline = System::externalities().execute_with(|| {
+//!     MockDep::mock_foo(|| true);
+//!     MockDep::mock_bar(|_, _| 23);
+//!     mock_builder::snapshot()
+//! });
+//!
+//! for case in cases {
+//!     System::externalities().execute_with(|| {
+//!         mock_builder::restore(&baseline);
+//!         MockDep::mock_foo(move || case.foo);
+//!         // ...
+//!     });
+//! }
+//! ```
+//!
+//! A snapshot contains the calls of the registered mocks, the locations where
+//! their `CallId`s were stored, i.e. the `CallIds` storage of a *mock pallet*,
+//! and their strict marks. Restoring it stores the `CallId`s again, so it must
+//! be done inside externalities: fresh ones, or a checkpoint of the ones where
+//! the snapshot was taken. The strict mode is not modified.
+
+use std::{cell::RefCell, collections::HashSet};
+
+use super::{local, storage, strict, CallId};
+
+/// Storage of a `CallId` at a location, replayed when a snapshot is restored.
+#[derive(Clone)]
+struct Insertion {
+	location: String,
+	call_id: CallId,
+	insert: fn(String, CallId),
+}
+
+thread_local! {
+	static INSERTIONS: RefCell<Vec<Insertion>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Mocks registered in the current thread when it was taken.
+/// See [`snapshot()`] and [`restore()`].
+#[derive(Clone)]
+pub struct Snapshot {
+	calls: storage::Registry,
+	insertions: Vec<Insertion>,
+	marked: HashSet<CallId>,
+}
+
+/// Take a snapshot of the mocks registered in the current thread.
+pub fn snapshot() -> Snapshot {
+	Snapshot {
+		calls: storage::snapshot(),
+		insertions: INSERTIONS.with(|insertions| insertions.borrow().clone()),
+		marked: strict::marked(),
+	}
+}
+
+/// Replace the mocks registered in the current thread by the ones of the
+/// snapshot, storing their `CallId`s again. Must not be called from a mock
+/// closure.
+pub fn restore(snapshot: &Snapshot) {
+	storage::restore(snapshot.calls.clone());
+	strict::restore_marked(snapshot.marked.clone());
+	local::clear();
+	clear();
+
+	for Insertion {
+		location,
+		call_id,
+		insert,
+	} in snapshot.insertions.iter().cloned()
+	{
+		self::insert(location, call_id, insert);
+	}
+}
+
+/// Store the `CallId` at the location, remembering it for the snapshots.
+pub(crate) fn insert(location: String, call_id: CallId, insert: fn(String, CallId)) {
+	insert(location.clone(), call_id);
+	INSERTIONS.with(|insertions| {
+		insertions.borrow_mut().push(Insertion {
+			location,
+			call_id,
+			insert,
+		})
+	});
+}
+
+/// `CallId`s stored at the locations of the given path, i.e: a pallet path.
+pub(crate) fn call_ids_at(path: &str) -> HashSet<CallId> {
+	INSERTIONS.with(|insertions| {
+		insertions
+			.borrow()
+			.iter()
+			.filter(|insertion| belongs_to(&insertion.location, path))
+			.map(|insertion| insertion.call_id)
+			.collect()
+	})
+}
+
+/// Forget the `CallId`s stored at the locations of the given path.
+pub(crate) fn clear_at(path: &str) {
+	INSERTIONS.with(|insertions| {
+		insertions
+			.borrow_mut()
+			.retain(|insertion| !belongs_to(&insertion.location, path))
+	});
+}
+
+fn belongs_to(location: &str, path: &str) -> bool {
+	location
+		.strip_prefix(path)
+		.map_or(false, |rest| rest.starts_with("::"))
+}
+
+/// Forget the stored `CallId`s of the current thread.
+pub(crate) fn clear() {
+	INSERTIONS.with(|insertions| insertions.borrow_mut().clear());
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	trait Oracle {
+		fn price(asset: u32) -> u64;
+	}
+
+	struct MockOracle;
+
+	impl MockOracle {
+		fn mock_price(f: impl Fn(u32) -> u64 + 'static) {
+			crate::register_call_local!(f);
+		}
+	}
+
+	impl Oracle for MockOracle {
+		fn price(asset: u32) -> u64 {
+			crate::execute_call_local!(asset)
+		}
+	}
+
+	#[test]
+	fn restored() {
+		MockOracle::mock_price(|_| 1);
+		let baseline = snapshot();
+
+		for price in [2, 3] {
+			restore(&baseline);
+			assert_eq!(MockOracle::price(0), 1);
+
+			MockOracle::mock_price(move |_| price);
+			assert_eq!(MockOracle::price(0), price);
+		}
+
+		restore(&baseline);
+		assert_eq!(MockOracle::price(0), 1);
+	}
+
+	#[test]
+	fn restored_executions() {
+		MockOracle::mock_price(|_| 1);
+		let baseline = snapshot();
+
+		MockOracle::price(0);
+		assert!(crate::unused_mocks().is_empty());
+
+		restore(&baseline);
+		assert_eq!(crate::unused_mocks().len(), 1);
+	}
+
+	#[test]
+	#[should_panic(expected = "Mock was not found")]
+	fn restored_without_later_mocks() {
+		let baseline = snapshot();
+		MockOracle::mock_price(|_| 1);
+
+		restore(&baseline);
+		MockOracle::price(0);
+	}
+}
@@ -6,17 +6,24 @@
 //! (`u64`).
 
 use std::{
-	cell::RefCell,
-	collections::HashMap,
+	cell::{Cell, RefCell},
+	collections::{HashMap, HashSet},
 	fmt,
+	panic::{self, AssertUnwindSafe},
 	sync::{Arc, Mutex},
 };
 
-use super::util::TypeSignature;
+use super::{
+	trace,
+	util::{DebugReprs, TypeSignature, NO_DEBUG},
+};
 
 /// Identify a call in the call storage
 pub type CallId = u64;
 
+/// Context added to the message of a panicking call.
+const PANIC_CONTEXT: &str = "\nMock panicked. Invocation: ";
+
 struct CallInfo {
 	/// Closure identification
 	ptr: u128,
@@ -26,12 +33,47 @@ struct CallInfo {
 	/// since the type at compiler time is lost in the `u128` representation of
 	/// the closure.
 	type_signature: TypeSignature,
+
+	/// Function that knows the closure type and is able to drop it.
+	drop_fn: unsafe fn(u128),
+
+	/// `DebugReprs` of the closure, with its types erased as the closure.
+	reprs: [usize; 2],
+}
+
+impl Drop for CallInfo {
+	fn drop(&mut self) {
+		// SAFETY: `drop_fn` was created along with `ptr` for the same closure type
+		// and `CallInfo` is the only owner of the closure, so it's dropped once.
+		unsafe { (self.drop_fn)(self.ptr) }
+	}
+}
+
+/// Registry entry of a call.
+/// The reporting information lives out of the mutex, so it can be read and
+/// modified while the call is executing, i.e: registering a call from a call.
+#[derive(Clone)]
+pub(crate) struct CallEntry {
+	call: Arc<Mutex<CallInfo>>,
+
+	/// Location where the call was registered, used for reporting.
+	location: String,
+
+	/// Times the call has been executed.
+	executions: u32,
+
+	/// The call was replaced by another one registered at the same location.
+	replaced: bool,
 }
 
-type Registry = HashMap<CallId, Arc<Mutex<CallInfo>>>;
+pub(crate) type Registry = HashMap<CallId, CallEntry>;
 
 thread_local! {
 	static CALLS: RefCell<Registry> = RefCell::new(HashMap::default());
+
+	// Identifiers are never reused, even after clearing the registry, to avoid
+	// old `CallId`s pointing to new closures.
+	static NEXT_CALL_ID: Cell<CallId> = const { Cell::new(0) };
 }
 
 #[derive(Debug, PartialEq)]
@@ -58,13 +100,25 @@ impl fmt::Display for Error {
 /// Register a call into the call storage.
 /// The registered call can be uniquely identified by the returned `CallId`.
 pub fn register_call<F: Fn(I) -> O + 'static, I, O>(f: F) -> CallId {
+	register_call_at(String::default(), f, DebugReprs::none())
+}
+
+/// Register a call into the call storage, with the location where it was
+/// registered and the `Debug` representations of its input and output.
+/// They are used to report the call.
+/// The registered call can be uniquely identified by the returned `CallId`.
+pub fn register_call_at<F: Fn(I) -> O + 'static, I, O>(
+	location: String,
+	f: F,
+	reprs: DebugReprs<I, O>,
+) -> CallId {
 	// We box the closure in order to store it in a fixed place of memory,
 	// and handle it in a more generic way without knowing the specific closure
 	// implementation.
 	let f = Box::new(f) as Box<dyn Fn(I) -> O>;
 
 	// We're only interested in the memory address of the closure.
-	// Box is never dropped after this call.
+	// Box is only dropped by `drop_fn` when the `CallInfo` is dropped.
 	let ptr: *const dyn Fn(I) -> O = Box::into_raw(f);
 
 	let call = CallInfo {
@@ -76,24 +130,62 @@ pub fn register_call<F: Fn(I) -> O + 'static, I, O>(f: F) -> CallId {
 		// Since we've lost the type representation at compile time, we need to store the type
 		// representation at runtime, in order to recover later the correct closure
 		type_signature: TypeSignature::new::<I, O>(),
+		drop_fn: drop_call::<I, O>,
+		// The same as with the closure, the types of the function pointers are
+		// recovered after checking the type signature.
+		reprs: [reprs.input as usize, reprs.output as usize],
 	};
 
+	let entry = CallEntry {
+		call: Arc::new(Mutex::new(call)),
+		location,
+		executions: 0,
+		replaced: false,
+	};
+
+	let call_id = NEXT_CALL_ID.with(|next| next.replace(next.get() + 1));
+
+	trace::registered(call_id, &entry.location);
+
 	CALLS.with(|state| {
 		let registry = &mut *state.borrow_mut();
-		let call_id = registry.len() as u64;
-		registry.insert(call_id, Arc::new(Mutex::new(call)));
+		registry.insert(call_id, entry);
 		call_id
 	})
 }
 
+/// # Safety
+/// `ptr` must be obtained from a `Box<dyn Fn(I) -> O>` not dropped yet.
+unsafe fn drop_call<I, O>(ptr: u128) {
+	#[allow(clippy::useless_transmute)] // Clippy hints something erroneous
+	let ptr: *mut dyn Fn(I) -> O = std::mem::transmute(ptr);
+	drop(Box::from_raw(ptr));
+}
+
+/// Execution of a call, with the `Debug` representations of its types.
+pub(crate) struct Execution<O> {
+	pub output: O,
+	pub input_repr: Option<String>,
+	pub output_repr: fn(&O) -> Option<String>,
+}
+
 /// Execute a call from the call storage identified by a `call_id`.
 pub fn execute_call<I, O>(call_id: CallId, input: I) -> Result<O, Error> {
+	execute_call_reported(call_id, input).map(|execution| execution.output)
+}
+
+/// Execute a call from the call storage identified by a `call_id`, returning
+/// the `Debug` representations of its input and output along with the output.
+pub(crate) fn execute_call_reported<I, O>(
+	call_id: CallId,
+	input: I,
+) -> Result<Execution<O>, Error> {
 	let expected_type_signature = TypeSignature::new::<I, O>();
 
-	let call = CALLS.with(|state| {
+	let (call, location) = CALLS.with(|state| {
 		let registry = &*state.borrow();
-		let call = registry.get(&call_id).ok_or(Error::CallNotFound)?;
-		Ok(call.clone())
+		let entry = registry.get(&call_id).ok_or(Error::CallNotFound)?;
+		Ok((entry.call.clone(), entry.location.clone()))
 	})?;
 
 	let call = call.lock().unwrap();
@@ -106,12 +198,20 @@ pub fn execute_call<I, O>(call_id: CallId, input: I) -> Result<O, Error> {
 		});
 	}
 
+	let invocation = CALLS.with(|state| {
+		let registry = &mut *state.borrow_mut();
+		let entry = registry.get_mut(&call_id).ok_or(Error::CallNotFound)?;
+		entry.executions += 1;
+		Ok(entry.executions)
+	})?;
+
 	// SAFETY:
 	// 1. The existence of this closure ptr in consequent calls is ensured
 	// thanks to Box::into_raw() at register_call(),
-	// which takes the Box ownership without dropping it. So, ptr exists forever.
+	// which takes the Box ownership without dropping it. The closure is only
+	// dropped with its `CallInfo`, which is alive while we hold this `Arc`.
 	// 2. The type of the transmuted call is ensured in runtime by the above type
-	// signature check.
+	// signature check, except for the lifetimes, see `TypeSignature`.
 	// 3. The pointer is correctly aligned because it was allocated by a Box.
 	// 4. The closure is called once at the same time thanks to the mutex.
 	let f: &dyn Fn(I) -> O = unsafe {
@@ -120,7 +220,143 @@ pub fn execute_call<I, O>(call_id: CallId, input: I) -> Result<O, Error> {
 		&*ptr
 	};
 
-	Ok(f(input))
+	// SAFETY: the pointers were obtained from a `DebugReprs<I, O>`, ensured by
+	// the above type signature check.
+	let reprs = unsafe {
+		DebugReprs::<I, O> {
+			input: std::mem::transmute::<usize, fn(&I) -> Option<String>>(call.reprs[0]),
+			output: std::mem::transmute::<usize, fn(&O) -> Option<String>>(call.reprs[1]),
+		}
+	};
+
+	// The input is consumed by the call, so its representation is obtained
+	// before, in case the call panics.
+	let input_repr = (reprs.input)(&input);
+
+	trace::executing(call_id, &location, input_repr.as_deref());
+
+	let output = match panic::catch_unwind(AssertUnwindSafe(|| f(input))) {
+		Ok(output) => output,
+		Err(payload) => {
+			// Released before panicking again to not poison the mutex.
+			drop(call);
+
+			let message = match payload.downcast::<String>() {
+				Ok(message) => *message,
+				Err(payload) => match payload.downcast::<&str>() {
+					Ok(message) => message.to_string(),
+					Err(payload) => panic::resume_unwind(payload),
+				},
+			};
+
+			// A mock called from this one already reported where it panicked.
+			if message.contains(PANIC_CONTEXT) {
+				panic::resume_unwind(Box::new(message));
+			}
+
+			panic!(
+				"{message}{PANIC_CONTEXT}{invocation}, input: {}, location: {location}",
+				input_repr.as_deref().unwrap_or(NO_DEBUG),
+			);
+		}
+	};
+
+	trace::executed(call_id, &location, || (reprs.output)(&output));
+
+	Ok(Execution {
+		output,
+		input_repr,
+		output_repr: reprs.output,
+	})
+}
+
+/// Mark the calls registered at `location` as replaced.
+/// Returns the ones that were not replaced yet.
+pub fn replace_calls_at(location: &str) -> Vec<CallId> {
+	CALLS.with(|state| {
+		let registry = &mut *state.borrow_mut();
+		registry
+			.iter_mut()
+			.filter(|(_, entry)| entry.location == location && !entry.replaced)
+			.map(|(call_id, entry)| {
+				entry.replaced = true;
+				*call_id
+			})
+			.collect()
+	})
+}
+
+/// Calls registered at `location` that have not been replaced yet.
+pub fn active_calls_at(location: &str) -> Vec<CallId> {
+	calls_where(|entry| entry.location == location && !entry.replaced)
+		.into_iter()
+		.map(|(call_id, _)| call_id)
+		.collect()
+}
+
+/// Calls that have been registered but never executed nor replaced,
+/// with their locations and ordered by registration.
+pub fn unused_calls() -> Vec<(CallId, String)> {
+	calls_where(|entry| entry.executions == 0 && !entry.replaced)
+}
+
+/// Calls that have been registered and not replaced, with their locations
+/// and ordered by registration.
+pub fn active_calls() -> Vec<(CallId, String)> {
+	calls_where(|entry| !entry.replaced)
+}
+
+fn calls_where(filter: impl Fn(&CallEntry) -> bool) -> Vec<(CallId, String)> {
+	CALLS.with(|state| {
+		let registry = &*state.borrow();
+		let mut calls = registry
+			.iter()
+			.filter(|(_, entry)| filter(entry))
+			.map(|(call_id, entry)| (*call_id, entry.location.clone()))
+			.collect::<Vec<_>>();
+
+		calls.sort();
+		calls
+	})
+}
+
+/// Check if the call is in the call storage.
+pub fn is_registered(call_id: CallId) -> bool {
+	CALLS.with(|state| state.borrow().contains_key(&call_id))
+}
+
+/// Copy of the call storage. The closures are shared with it.
+pub(crate) fn snapshot() -> Registry {
+	CALLS.with(|state| state.borrow().clone())
+}
+
+/// Replace the call storage by a copy obtained with [`snapshot()`].
+pub(crate) fn restore(registry: Registry) {
+	let replaced = CALLS.with(|state| std::mem::replace(&mut *state.borrow_mut(), registry));
+	drop(replaced);
+}
+
+/// Remove the given calls from the call storage, dropping their closures once
+/// they are no longer executing.
+pub fn remove_calls(call_ids: &HashSet<CallId>) {
+	// The calls are taken before dropping them, as in `clear()`.
+	let removed = CALLS.with(|state| {
+		let registry = &mut *state.borrow_mut();
+		call_ids
+			.iter()
+			.filter_map(|call_id| registry.remove(call_id))
+			.collect::<Vec<_>>()
+	});
+	drop(removed);
+}
+
+/// Remove all calls from the call storage, dropping their closures once they
+/// are no longer executing.
+pub fn clear() {
+	// The registry is taken before dropping the calls, so closures capturing
+	// calls can be dropped without borrowing the registry twice.
+	let registry = CALLS.with(|state| std::mem::take(&mut *state.borrow_mut()));
+	drop(registry);
 }
 
 #[cfg(test)]
@@ -166,6 +402,132 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn mutable_reference() {
+		let call_id_1 = register_call(|(buf, n): (&mut Vec<u8>, u8)| {
+			buf.push(n);
+			buf.len()
+		});
+
+		let mut buf = vec![1u8];
+		assert_eq!(execute_call::<_, usize>(call_id_1, (&mut buf, 2u8)), Ok(2));
+		assert_eq!(buf, vec![1, 2]);
+	}
+
+	#[test]
+	fn unused() {
+		let call_id_1 = register_call_at("first".into(), |n: u8| n, DebugReprs::none());
+		let call_id_2 = register_call_at("second".into(), |n: u8| n, DebugReprs::none());
+		execute_call::<_, u8>(call_id_1, 2u8).unwrap();
+
+		assert_eq!(unused_calls(), vec![(call_id_2, String::from("second"))]);
+	}
+
+	#[test]
+	fn replaced() {
+		let call_id_1 = register_call_at("first".into(), |n: u8| n, DebugReprs::none());
+
+		assert_eq!(active_calls_at("first"), vec![call_id_1]);
+		assert_eq!(replace_calls_at("first"), vec![call_id_1]);
+		assert_eq!(active_calls_at("first"), vec![]);
+		assert_eq!(replace_calls_at("first"), vec![]);
+		assert_eq!(replace_calls_at("second"), vec![]);
+
+		assert_eq!(unused_calls(), vec![]);
+	}
+
+	#[test]
+	fn active() {
+		let call_id_1 = register_call_at("first".into(), |n: u8| n, DebugReprs::none());
+		let call_id_2 = register_call_at("second".into(), |n: u8| n, DebugReprs::none());
+		execute_call::<_, u8>(call_id_1, 2u8).unwrap();
+		replace_calls_at("second");
+		let call_id_3 = register_call_at("second".into(), |n: u8| n, DebugReprs::none());
+
+		assert_eq!(
+			active_calls(),
+			vec![
+				(call_id_1, String::from("first")),
+				(call_id_3, String::from("second"))
+			]
+		);
+		assert!(is_registered(call_id_2));
+	}
+
+	#[test]
+	fn removed() {
+		let call_id_1 = register_call_at("first".into(), |n: u8| n, DebugReprs::none());
+		let call_id_2 = register_call_at("second".into(), |n: u8| n, DebugReprs::none());
+
+		remove_calls(&HashSet::from([call_id_1]));
+
+		assert!(!is_registered(call_id_1));
+		assert_eq!(
+			execute_call::<_, u8>(call_id_1, 2u8),
+			Err(Error::CallNotFound)
+		);
+		assert_eq!(execute_call::<_, u8>(call_id_2, 2u8), Ok(2));
+	}
+
+	#[test]
+	fn cleared() {
+		let value = std::rc::Rc::new(());
+		let captured = value.clone();
+		let call_id_1 = register_call(move |n: u8| {
+			let _ = &captured;
+			n
+		});
+
+		clear();
+
+		assert_eq!(std::rc::Rc::strong_count(&value), 1);
+		assert_eq!(
+			execute_call::<_, u8>(call_id_1, 2u8),
+			Err(Error::CallNotFound)
+		);
+		assert_ne!(register_call(|n: u8| n), call_id_1);
+	}
+
+	#[test]
+	fn panic_context() {
+		let f = |n: u8| {
+			assert!(n < 3, "too big");
+			n
+		};
+		let reprs = crate::debug_reprs!(&f);
+		let call_id_1 = register_call_at("first".into(), f, reprs);
+		execute_call::<_, u8>(call_id_1, 2u8).unwrap();
+
+		let payload = panic::catch_unwind(|| execute_call::<_, u8>(call_id_1, 3u8)).unwrap_err();
+
+		assert_eq!(
+			payload.downcast_ref::<String>().unwrap(),
+			"too big\nMock panicked. Invocation: 2, input: 3, location: first"
+		);
+		assert_eq!(execute_call::<_, u8>(call_id_1, 1u8), Ok(1));
+	}
+
+	#[test]
+	fn panic_context_of_nested_call() {
+		let f = |n: u8| {
+			assert!(n < 3, "too big");
+			n
+		};
+		let reprs = crate::debug_reprs!(&f);
+		let call_id_1 = register_call_at("inner".into(), f, reprs);
+
+		let f = move |n: u8| execute_call::<_, u8>(call_id_1, n).unwrap();
+		let reprs = crate::debug_reprs!(&f);
+		let call_id_2 = register_call_at("outer".into(), f, reprs);
+
+		let payload = panic::catch_unwind(|| execute_call::<_, u8>(call_id_2, 3u8)).unwrap_err();
+
+		assert_eq!(
+			payload.downcast_ref::<String>().unwrap(),
+			"too big\nMock panicked. Invocation: 1, input: 3, location: inner"
+		);
+	}
+
 	#[test]
 	fn no_registered() {
 		let call_id_1 = 42;
@@ -0,0 +1,135 @@
+//! In strict mode, registering a mock over an active one without
+//! [`replace()`] panics, and [`verify()`](crate::verify) panics if any mock
+//! was never executed. The mode applies to the mocks registered after enabling
+//! it, except the ones registered inside [`lax()`](crate::strict::lax), and
+//! lasts until [`clear()`](crate::clear) is called.
+//!
+//! Functions registering default behaviors, as the `use_*()` functions of
+//! the ready-made *mock pallets*, register them inside
+//! [`lax()`](crate::strict::lax). A test can leave any of them unused, or
+//! mock it again to change its behavior, even in strict mode.
+
+use std::{
+	cell::{Cell, RefCell},
+	collections::HashSet,
+	thread::LocalKey,
+};
+
+use super::{location::FunctionLocation, CallId};
+
+enum Mode {
+	Disabled,
+	All,
+	Pallets(Vec<String>),
+}
+
+thread_local! {
+	static MODE: RefCell<Mode> = const { RefCell::new(Mode::Disabled) };
+	static STRICT_CALLS: RefCell<HashSet<CallId>> = RefCell::new(HashSet::default());
+	static REPLACING: Cell<u32> = const { Cell::new(0) };
+	static LAX: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Enable the strict mode for all mock pallets in the current thread.
+pub fn enable() {
+	MODE.with(|mode| *mode.borrow_mut() = Mode::All);
+}
+
+/// Enable the strict mode for the mock pallet `P` in the current thread,
+/// i.e: `enable_for::<MockDep>()`.
+pub fn enable_for<P>() {
+	let pallet = std::any::type_name::<P>().to_owned();
+	MODE.with(|mode| {
+		let mode = &mut *mode.borrow_mut();
+		match mode {
+			Mode::Disabled => *mode = Mode::Pallets(vec![pallet]),
+			Mode::All => (),
+			Mode::Pallets(pallets) => pallets.push(pallet),
+		}
+	});
+}
+
+/// Allow the mocks registered inside `f` to replace the active ones.
+///
+/// ```ignore
+/// MyMock::mock_set(|value| mock_builder::replace(|| MyMock::mock_get(move || value)));
+/// ```
+pub fn replace<R>(f: impl FnOnce() -> R) -> R {
+	let _nested = Nested::enter(&REPLACING);
+	f()
+}
+
+/// Exclude the mocks registered inside `f` from the strict mode, i.e: default
+/// behaviors that a test can leave unused or replace without [`replace()`].
+pub fn lax<R>(f: impl FnOnce() -> R) -> R {
+	let _nested = Nested::enter(&LAX);
+	f()
+}
+
+/// Nesting level in a counter, left when dropped, even if a mock panics.
+struct Nested(&'static LocalKey<Cell<u32>>);
+
+impl Nested {
+	fn enter(counter: &'static LocalKey<Cell<u32>>) -> Self {
+		counter.with(|level| level.set(level.get() + 1));
+		Self(counter)
+	}
+}
+
+impl Drop for Nested {
+	fn drop(&mut self) {
+		self.0.with(|level| level.set(level.get() - 1));
+	}
+}
+
+pub(crate) fn is_enabled_for(location: &FunctionLocation) -> bool {
+	if LAX.with(|lax| lax.get() > 0) {
+		return false;
+	}
+
+	MODE.with(|mode| match &*mode.borrow() {
+		Mode::Disabled => false,
+		Mode::All => true,
+		Mode::Pallets(pallets) => pallets.iter().any(|pallet| location.belongs_to(pallet)),
+	})
+}
+
+pub(crate) fn is_replacing() -> bool {
+	REPLACING.with(|replacing| replacing.get() > 0)
+}
+
+pub(crate) fn mark(call_id: CallId) {
+	STRICT_CALLS.with(|calls| calls.borrow_mut().insert(call_id));
+}
+
+pub(crate) fn is_marked(call_id: CallId) -> bool {
+	STRICT_CALLS.with(|calls| calls.borrow().contains(&call_id))
+}
+
+pub(crate) fn marked() -> HashSet<CallId> {
+	STRICT_CALLS.with(|calls| calls.borrow().clone())
+}
+
+pub(crate) fn restore_marked(marked: HashSet<CallId>) {
+	STRICT_CALLS.with(|calls| *calls.borrow_mut() = marked);
+}
+
+pub(crate) fn reset() {
+	MODE.with(|mode| *mode.borrow_mut() = Mode::Disabled);
+	STRICT_CALLS.with(|calls| calls.borrow_mut().clear());
+}
+
+#[cfg(test)]
+mod tests {
+	use std::panic::catch_unwind;
+
+	use super::*;
+
+	#[test]
+	fn left_on_panic() {
+		assert!(catch_unwind(|| replace(|| lax(|| panic!("mock panicked")))).is_err());
+
+		assert_eq!(REPLACING.with(Cell::get), 0);
+		assert_eq!(LAX.with(Cell::get), 0);
+	}
+}
@@ -0,0 +1,168 @@
+//! Without the `std` feature, mocks can not be registered nor executed, but
+//! *mock pallets* still compile, i.e. as part of a runtime built for `wasm`.
+//! The register macros do nothing, and the execute macros panic or return the
+//! output given as `no_std = <output>`:
+//!
+//! ```ignore
+//! impl<T: Config> TraitA for Pallet<T> {
+//!     fn foo(a: String, b: u64) -> DispatchResult {
+//!         execute_call!((a, b), no_std = Err(DispatchError::Unavailable))
+//!     }
+//! }
+//! ```
+//!
+//! The outputs given as `default = <output>` are never used, as no mock
+//! pallet can enable its defaults.
+
+/// Identify a call in the call storage
+pub type CallId = u64;
+
+/// Default outputs of the mocks, never used without the `std` feature.
+pub mod defaults {
+	/// Does nothing without the `std` feature.
+	pub fn enable_for<P>() {}
+
+	/// Always `false` without the `std` feature.
+	pub fn is_enabled_for<P>() -> bool {
+		false
+	}
+}
+
+/// Strict mode, never enabled without the `std` feature.
+pub mod strict {
+	/// Only calls `f` without the `std` feature.
+	pub fn lax<R>(f: impl FnOnce() -> R) -> R {
+		f()
+	}
+}
+
+/// Called by the execute macros without an output for builds without `std`.
+pub fn unavailable() -> ! {
+	unimplemented!("Mocks are only available with the `std` feature of `mock-builder`")
+}
+
+#[macro_export]
+macro_rules! register_call {
+	($f:expr) => {{
+		let _ = $f;
+	}};
+}
+
+#[macro_export]
+macro_rules! register_call_instance {
+	($f:expr) => {{
+		let _ = $f;
+	}};
+}
+
+#[macro_export]
+macro_rules! register_call_any {
+	($f:expr) => {{
+		let _ = $f;
+	}};
+}
+
+#[macro_export]
+macro_rules! register_call_instance_any {
+	($f:expr) => {{
+		let _ = $f;
+	}};
+}
+
+#[macro_export]
+macro_rules! execute_call {
+	($input:expr, $(default = $default:expr,)? $(any,)? no_std = $output:expr) => {{
+		let _ = $input;
+		$output
+	}};
+	($input:expr $(, default = $default:expr)? $(, any)?) => {{
+		let _ = $input;
+		$crate::stub::unavailable()
+	}};
+}
+
+#[macro_export]
+macro_rules! execute_call_instance {
+	($($args:tt)*) => {
+		$crate::execute_call!($($args)*)
+	};
+}
+
+#[macro_export]
+macro_rules! execute_call_with_event {
+	($($args:tt)*) => {{
+		let _ = |event: $crate::MockEvent| Pallet::<T>::deposit_event(event.into());
+		$crate::execute_call!($($args)*)
+	}};
+}
+
+#[macro_export]
+macro_rules! execute_call_instance_with_event {
+	($($args:tt)*) => {{
+		let _ = |event: $crate::MockEvent| Pallet::<T, I>::deposit_event(event.into());
+		$crate::execute_call!($($args)*)
+	}};
+}
+
+#[macro_export]
+macro_rules! register_dispatch {
+	($id:expr, $f:expr) => {{
+		let _ = ($id, $f);
+	}};
+}
+
+#[macro_export]
+macro_rules! register_dispatch_instance {
+	($id:expr, $f:expr) => {{
+		let _ = ($id, $f);
+	}};
+}
+
+#[macro_export]
+macro_rules! execute_dispatch {
+	($id:expr, $origin:expr, no_std = $output:expr) => {{
+		let _ = ($id, $origin);
+		$output
+	}};
+	($id:expr, $origin:expr) => {{
+		let _ = ($id, $origin);
+		$crate::stub::unavailable()
+	}};
+}
+
+#[macro_export]
+macro_rules! execute_dispatch_instance {
+	($($args:tt)*) => {
+		$crate::execute_dispatch!($($args)*)
+	};
+}
+
+#[macro_export]
+macro_rules! register_call_local {
+	($f:expr) => {{
+		let _ = $f;
+	}};
+}
+
+#[macro_export]
+macro_rules! execute_call_local {
+	($input:expr $(, default = $default:expr)?) => {{
+		let _ = $input;
+		$crate::stub::unavailable()
+	}};
+}
+
+#[macro_export]
+macro_rules! register_object_call {
+	($f:expr) => {{
+		let _ = $f;
+	}};
+}
+
+#[macro_export]
+macro_rules! execute_object_call {
+	($object:expr, $input:expr) => {{
+		let _ = ($object, $input);
+		$crate::stub::unavailable()
+	}};
+}
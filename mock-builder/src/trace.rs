@@ -0,0 +1,61 @@
+//! Trace events emitted on each mock registration and execution when the
+//! `tracing` feature is enabled, showing the interaction timeline with the
+//! mocked dependencies:
+//!
+//! ```sh
+//! RUST_LOG=mock_builder=trace cargo test --features mock-builder/tracing
+//! ```
+//!
+//! Without the feature, all these functions do nothing.
+
+use super::storage::CallId;
+#[cfg(feature = "tracing")]
+use super::util::NO_DEBUG;
+
+/// Target of the trace events.
+pub const TARGET: &str = "mock_builder";
+
+/// Initialize a subscriber printing the trace events filtered by `RUST_LOG`.
+/// It does nothing if there is already one.
+pub fn init() {
+	#[cfg(feature = "tracing")]
+	sp_tracing::try_init_simple();
+}
+
+#[allow(unused_variables)]
+pub(crate) fn registered(call_id: CallId, location: &str) {
+	#[cfg(feature = "tracing")]
+	sp_tracing::trace!(
+		target: TARGET,
+		"Mock registered. CallId: {call_id}, location: {location}"
+	);
+}
+
+#[allow(unused_variables)]
+pub(crate) fn executing(call_id: CallId, location: &str, input: Option<&str>) {
+	#[cfg(feature = "tracing")]
+	sp_tracing::trace!(
+		target: TARGET,
+		"Mock executing. CallId: {call_id}, input: {}, location: {location}",
+		input.unwrap_or(NO_DEBUG),
+	);
+}
+
+#[allow(unused_variables)]
+pub(crate) fn executed(call_id: CallId, location: &str, output: impl FnOnce() -> Option<String>) {
+	#[cfg(feature = "tracing")]
+	sp_tracing::trace!(
+		target: TARGET,
+		"Mock executed. CallId: {call_id}, output: {}, location: {location}",
+		output().as_deref().unwrap_or(NO_DEBUG),
+	);
+}
+
+#[allow(unused_variables)]
+pub(crate) fn unused(location: &str) {
+	#[cfg(feature = "tracing")]
+	sp_tracing::debug!(
+		target: TARGET,
+		"Mock registered but never executed. Location: {location}"
+	);
+}
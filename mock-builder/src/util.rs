@@ -1,5 +1,16 @@
 use std::fmt;
 
+/// Input and output types of a call, compared by their names.
+///
+/// # Safety caveat
+/// Type names have no lifetimes, so the signature of `&'static u8` is the
+/// same as the one of `&'a u8`, and an execution is given the registered
+/// closure whatever the lifetimes of its input and output. It's only sound
+/// for closures valid for any lifetime, i.e. higher-ranked closures as
+/// `for<'a> Fn(&'a mut Vec<u8>) -> &'a u8`, which is what the `mock_*()`
+/// functions take when their lifetimes are elided. A `mock_*()` function
+/// taking a closure with a fixed lifetime, as `Fn(&'static u8)`, lets the
+/// closure keep an input that doesn't live as long.
 #[derive(Debug, PartialEq, Clone)]
 pub struct TypeSignature(String);
 
@@ -18,3 +29,147 @@ impl fmt::Display for TypeSignature {
 		write!(f, "{}", self.0)
 	}
 }
+
+/// Wrapper used to obtain the `Debug` representation of a value only if its
+/// type implements `Debug`. See [`debug_repr!()`](crate::debug_repr).
+///
+/// The trick relies on the method resolution: [`WithDebug`] is implemented
+/// for `Inspect<T>` and [`WithoutDebug`] for `&Inspect<T>`, so the first one
+/// is preferred when both are available. It only works where the type is
+/// known, which is why it's used through a macro.
+pub struct Inspect<'a, T>(pub &'a T);
+
+pub trait WithDebug {
+	fn debug_repr(&self) -> Option<String>;
+}
+
+impl<T: fmt::Debug> WithDebug for Inspect<'_, T> {
+	fn debug_repr(&self) -> Option<String> {
+		Some(format!("{:?}", self.0))
+	}
+}
+
+pub trait WithoutDebug {
+	fn debug_repr(&self) -> Option<String>;
+}
+
+impl<T> WithoutDebug for &Inspect<'_, T> {
+	fn debug_repr(&self) -> Option<String> {
+		None
+	}
+}
+
+/// Obtain the `Debug` representation of a reference to a value as
+/// `Some(String)`, or `None` if the value type does not implement `Debug`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! debug_repr {
+	($value:expr) => {{
+		#[allow(unused_imports)]
+		use $crate::util::{WithDebug as _, WithoutDebug as _};
+		(&$crate::util::Inspect($value)).debug_repr()
+	}};
+}
+
+/// Shown instead of the `Debug` representation of a value without it.
+pub const NO_DEBUG: &str = "<no debug>";
+
+/// Functions obtaining the `Debug` representation of the input and output of
+/// a call, if their types implement `Debug`. See
+/// [`debug_reprs!()`](crate::debug_reprs).
+pub struct DebugReprs<I, O> {
+	pub input: fn(&I) -> Option<String>,
+	pub output: fn(&O) -> Option<String>,
+}
+
+impl<I, O> DebugReprs<I, O> {
+	/// Create the representations for the input and output types of `f`.
+	/// Taking `f` allows to infer those types before checking the closures
+	/// that obtain the representations.
+	pub fn of<F: Fn(I) -> O>(
+		_: &F,
+		input: fn(&I) -> Option<String>,
+		output: fn(&O) -> Option<String>,
+	) -> Self {
+		Self { input, output }
+	}
+
+	/// Representations for types without `Debug`.
+	pub fn none() -> Self {
+		Self {
+			input: |_| None,
+			output: |_| None,
+		}
+	}
+}
+
+impl<I, O> Clone for DebugReprs<I, O> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<I, O> Copy for DebugReprs<I, O> {}
+
+/// Obtain the [`DebugReprs`] of a reference to a closure, or of the types
+/// inferred from where they are used if no closure is given.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! debug_reprs {
+	() => {
+		$crate::util::DebugReprs {
+			input: |input| $crate::debug_repr!(input),
+			output: |output| $crate::debug_repr!(output),
+		}
+	};
+	($f:expr) => {
+		$crate::util::DebugReprs::of(
+			$f,
+			|input| $crate::debug_repr!(input),
+			|output| $crate::debug_repr!(output),
+		)
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use super::TypeSignature;
+
+	struct NoDebug;
+
+	fn generic<T>(value: T) -> Option<String> {
+		crate::debug_repr!(&value)
+	}
+
+	#[test]
+	fn debug_repr() {
+		assert_eq!(crate::debug_repr!(&(23, "a")), Some("(23, \"a\")".into()));
+		assert_eq!(crate::debug_repr!(&NoDebug), None);
+		assert_eq!(generic(23), None);
+	}
+
+	#[test]
+	fn debug_reprs() {
+		let f = |(a, _): (u8, NoDebug)| a;
+		let reprs = crate::debug_reprs!(&f);
+
+		assert_eq!((reprs.input)(&(23, NoDebug)), None);
+		assert_eq!((reprs.output)(&23), Some("23".into()));
+	}
+
+	fn signature_of<'a>(_: &'a mut Vec<u8>) -> TypeSignature {
+		TypeSignature::new::<(&'a mut Vec<u8>, &'static u8), &'a u8>()
+	}
+
+	#[test]
+	fn type_signature_without_lifetimes() {
+		assert_eq!(
+			signature_of(&mut Vec::new()),
+			TypeSignature::new::<(&mut Vec<u8>, &u8), &u8>()
+		);
+		assert_ne!(
+			signature_of(&mut Vec::new()),
+			TypeSignature::new::<(&Vec<u8>, &u8), &u8>()
+		);
+	}
+}
@@ -0,0 +1,176 @@
+pub trait TraitA {
+	fn foo(p1: String, p2: Option<u64>);
+	fn bar(p1: u64) -> bool;
+}
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet_mock_test {
+	use frame_support::pallet_prelude::*;
+	use mock_builder::{execute_call_with_event, register_call, MockEvent};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	type CallIds<T: Config> = StorageMap<_, _, String, mock_builder::CallId>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		Mock(MockEvent),
+	}
+
+	impl<T: Config> From<MockEvent> for Event<T> {
+		fn from(event: MockEvent) -> Self {
+			Event::Mock(event)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn mock_foo(f: impl Fn(String, Option<u64>) + 'static) {
+			register_call!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_bar(f: impl Fn(u64) -> bool + 'static) {
+			register_call!(f);
+		}
+	}
+
+	impl<T: Config> super::TraitA for Pallet<T> {
+		fn foo(a: String, b: Option<u64>) {
+			execute_call_with_event!((a, b))
+		}
+
+		fn bar(a: u64) -> bool {
+			execute_call_with_event!(a)
+		}
+	}
+}
+
+#[frame_support::pallet]
+pub mod my_pallet {
+	use frame_support::pallet_prelude::*;
+
+	use super::TraitA;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		type Action: TraitA;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		Done,
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn my_call(name: &str, value: u64) {
+			T::Action::foo(name.into(), Some(value));
+			Self::deposit_event(Event::Done);
+			T::Action::bar(value);
+		}
+	}
+}
+
+mod mock {
+	use frame_support::derive_impl;
+
+	use super::{my_pallet, pallet_mock_test};
+
+	frame_support::construct_runtime!(
+		pub struct Runtime {
+			System: frame_system,
+			MockTest: pallet_mock_test,
+			MyPallet: my_pallet,
+		}
+	);
+
+	#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+	impl frame_system::Config for Runtime {
+		type Block = frame_system::mocking::MockBlock<Runtime>;
+	}
+
+	impl pallet_mock_test::Config for Runtime {
+		type RuntimeEvent = RuntimeEvent;
+	}
+
+	impl my_pallet::Config for Runtime {
+		type Action = pallet_mock_test::Pallet<Runtime>;
+		type RuntimeEvent = RuntimeEvent;
+	}
+}
+
+mod test {
+	use mock_builder::MockEvent;
+
+	use super::{mock::*, my_pallet, pallet_mock_test};
+
+	#[test]
+	fn called_event() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_bar(|_| true);
+
+			<MockTest as super::TraitA>::bar(42);
+
+			System::assert_has_event(
+				pallet_mock_test::Event::Mock(MockEvent::called("TraitA::bar", &42u64)).into(),
+			);
+		});
+	}
+
+	#[test]
+	fn interleaved_events() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_foo(|_, _| ());
+			MockTest::mock_bar(|_| true);
+
+			MyPallet::my_call("hello", 42);
+
+			let events = System::events()
+				.into_iter()
+				.map(|record| record.event)
+				.collect::<Vec<_>>();
+
+			assert_eq!(
+				events,
+				vec![
+					RuntimeEvent::MockTest(pallet_mock_test::Event::Mock(MockEvent::called(
+						"TraitA::foo",
+						&(String::from("hello"), Some(42u64))
+					))),
+					RuntimeEvent::MyPallet(my_pallet::Event::Done),
+					RuntimeEvent::MockTest(pallet_mock_test::Event::Mock(MockEvent::called(
+						"TraitA::bar",
+						&42u64
+					))),
+				]
+			);
+		});
+	}
+
+	#[test]
+	fn not_called_with_other_input() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_bar(|_| true);
+
+			<MockTest as super::TraitA>::bar(23);
+
+			let expected: RuntimeEvent =
+				pallet_mock_test::Event::Mock(MockEvent::called("TraitA::bar", &42u64)).into();
+
+			assert!(!System::events()
+				.into_iter()
+				.any(|record| record.event == expected));
+		});
+	}
+}
@@ -7,8 +7,12 @@ pub trait TraitA {
 pub trait TraitB {
 	fn qux(p1: String) -> bool;
 	fn generic_input<A: Into<i32>>(a: A, b: impl Into<u32>) -> usize;
-	fn generic_output<A: Into<i32>>() -> A;
+	fn generic_output<A: Into<i32> + 'static>() -> A;
 	fn reference(a: &i32) -> &i32;
+	fn mutable_reference(buf: &mut Vec<u8>, value: u8);
+	fn references<'a>(buf: &'a mut Vec<u8>, value: &u8) -> &'a u8;
+	fn iter() -> impl Iterator<Item = u32>;
+	fn iter_reference(values: &[u32]) -> impl Iterator<Item = &u32> + '_;
 	fn same_name(p1: i32) -> bool;
 }
 
@@ -23,8 +27,14 @@ pub trait Storage {
 
 #[frame_support::pallet(dev_mode)]
 pub mod pallet_mock_test {
+	use std::any::Any;
+
 	use frame_support::pallet_prelude::*;
-	use mock_builder::{execute_call, register_call};
+	use frame_system::pallet_prelude::*;
+	use mock_builder::{
+		execute_call, execute_dispatch, register_call, register_call_any, register_dispatch,
+		MockIter,
+	};
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {}
@@ -52,14 +62,42 @@ pub mod pallet_mock_test {
 			register_call!(move |(a, b)| f(a, b));
 		}
 
-		pub fn mock_generic_output<A: Into<i32>>(f: impl Fn() -> A + 'static) {
+		pub fn mock_generic_output<A: Into<i32> + 'static>(f: impl Fn() -> A + 'static) {
 			register_call!(move |()| f());
 		}
 
+		pub fn mock_generic_input_any(f: impl Fn(&str) -> Box<dyn Any> + 'static) {
+			register_call_any!(f);
+		}
+
+		pub fn mock_generic_output_any(f: impl Fn(&str) -> Box<dyn Any> + 'static) {
+			register_call_any!(f);
+		}
+
 		pub fn mock_reference(f: impl Fn(&i32) -> &i32 + 'static) {
 			register_call!(f);
 		}
 
+		pub fn mock_mutable_reference(f: impl Fn(&mut Vec<u8>, u8) + 'static) {
+			register_call!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_references(
+			f: impl for<'a, 'b> Fn(&'a mut Vec<u8>, &'b u8) -> &'a u8 + 'static,
+		) {
+			register_call!(move |(a, b)| f(a, b));
+		}
+
+		pub fn mock_iter(f: impl Fn() -> MockIter<'static, u32> + 'static) {
+			register_call!(move |()| f());
+		}
+
+		pub fn mock_iter_reference(
+			f: impl for<'a> Fn(&'a [u32]) -> MockIter<'a, &'a u32> + 'static,
+		) {
+			register_call!(f);
+		}
+
 		pub fn mock_set(f: impl Fn(i32) + 'static) {
 			register_call!(f);
 		}
@@ -82,15 +120,34 @@ pub mod pallet_mock_test {
 		pub fn mock_TraitGen_generic(f: impl Fn() -> u32 + 'static) {
 			register_call!(move |()| f());
 		}
+
+		pub fn use_defaults() {
+			mock_builder::defaults::enable_for::<Self>();
+		}
+
+		pub fn mock_dispatch_with(
+			id: u32,
+			f: impl Fn(OriginFor<T>) -> DispatchResultWithPostInfo + 'static,
+		) {
+			register_dispatch!(id, f);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		pub fn mock_dispatch(origin: OriginFor<T>, id: u32) -> DispatchResultWithPostInfo {
+			execute_dispatch!(id, origin)
+		}
 	}
 
 	impl<T: Config> super::TraitA for Pallet<T> {
 		fn foo(a: String, b: Option<u64>) {
-			execute_call!((a, b))
+			execute_call!((a, b), default = ())
 		}
 
 		fn bar(a: u64, b: bool) -> Result<(), String> {
-			execute_call!((a, b))
+			execute_call!((a, b), default = Ok(()))
 		}
 
 		fn same_name(a: bool, b: i32) -> usize {
@@ -100,21 +157,39 @@ pub mod pallet_mock_test {
 
 	impl<T: Config> super::TraitB for Pallet<T> {
 		fn qux(a: String) -> bool {
-			execute_call!(a)
+			execute_call!(a, default = Default::default())
 		}
 
 		fn generic_input<A: Into<i32>>(a: A, b: impl Into<u32>) -> usize {
-			execute_call!((a, b))
+			execute_call!((a, b), any)
 		}
 
-		fn generic_output<A: Into<i32>>() -> A {
-			execute_call!(())
+		fn generic_output<A: Into<i32> + 'static>() -> A {
+			execute_call!((), any)
 		}
 
 		fn reference(a: &i32) -> &i32 {
 			execute_call!(a)
 		}
 
+		fn mutable_reference(a: &mut Vec<u8>, b: u8) {
+			execute_call!((a, b))
+		}
+
+		fn references<'a>(a: &'a mut Vec<u8>, b: &u8) -> &'a u8 {
+			execute_call!((a, b))
+		}
+
+		fn iter() -> impl Iterator<Item = u32> {
+			let iter: MockIter<u32> = execute_call!(());
+			iter
+		}
+
+		fn iter_reference(a: &[u32]) -> impl Iterator<Item = &u32> + '_ {
+			let iter: MockIter<&u32> = execute_call!(a);
+			iter
+		}
+
 		fn same_name(a: i32) -> bool {
 			execute_call!(a)
 		}
@@ -137,6 +212,45 @@ pub mod pallet_mock_test {
 	}
 }
 
+#[frame_support::pallet(dev_mode)]
+pub mod pallet_mock_instance {
+	use frame_support::pallet_prelude::*;
+	use mock_builder::{execute_call_instance, register_call_instance};
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(_);
+
+	#[pallet::storage]
+	type CallIds<T: Config<I>, I: 'static = ()> = StorageMap<_, _, String, mock_builder::CallId>;
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		pub fn use_defaults() {
+			mock_builder::defaults::enable_for::<Self>();
+		}
+
+		pub fn mock_set(f: impl Fn(i32) + 'static) {
+			register_call_instance!(f);
+		}
+
+		pub fn mock_get(f: impl Fn() -> i32 + 'static) {
+			register_call_instance!(move |()| f());
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> super::Storage for Pallet<T, I> {
+		fn set(a: i32) {
+			execute_call_instance!(a)
+		}
+
+		fn get() -> i32 {
+			execute_call_instance!((), default = 0)
+		}
+	}
+}
+
 #[frame_support::pallet]
 pub mod my_pallet {
 	use super::{TraitA, TraitB};
@@ -161,12 +275,14 @@ pub mod my_pallet {
 mod mock {
 	use frame_support::derive_impl;
 
-	use super::{my_pallet, pallet_mock_test};
+	use super::{my_pallet, pallet_mock_instance, pallet_mock_test};
 
 	frame_support::construct_runtime!(
 		pub struct Runtime {
 			System: frame_system,
 			MockTest: pallet_mock_test,
+			MockInstance1: pallet_mock_instance<Instance1>,
+			MockInstance2: pallet_mock_instance<Instance2>,
 			MyPallet: my_pallet,
 		}
 	);
@@ -178,15 +294,29 @@ mod mock {
 
 	impl pallet_mock_test::Config for Runtime {}
 
+	impl pallet_mock_instance::Config<pallet_mock_instance::Instance1> for Runtime {}
+
+	impl pallet_mock_instance::Config<pallet_mock_instance::Instance2> for Runtime {}
+
 	impl my_pallet::Config for Runtime {
 		type ActionAB = pallet_mock_test::Pallet<Runtime>;
 	}
+
+	impl mock_builder::BenchmarkDefaults for Runtime {
+		fn register_defaults() {
+			MockTest::mock_qux(|p1| &p1 == "hello");
+		}
+	}
 }
 
 mod test {
-	use frame_support::assert_ok;
+	use frame_support::{
+		assert_err, assert_ok,
+		dispatch::{Pays, PostDispatchInfo},
+	};
+	use sp_runtime::{traits::Dispatchable, DispatchError};
 
-	use super::{mock::*, Storage, TraitA, TraitB, TraitGen};
+	use super::{mock::*, pallet_mock_test, Storage, TraitA, TraitB, TraitGen};
 
 	#[test]
 	fn basic() {
@@ -221,6 +351,16 @@ mod test {
 		});
 	}
 
+	#[test]
+	#[should_panic(expected = "Mock panicked. Invocation: 1, input: (\"bye\", Some(42))")]
+	fn wrong_with_context() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_foo(|p1, _| assert_eq!("hello", &p1));
+
+			assert_ok!(MyPallet::my_call("bye".into(), 42));
+		});
+	}
+
 	#[test]
 	#[should_panic]
 	fn mock_not_configured() {
@@ -281,6 +421,50 @@ mod test {
 		});
 	}
 
+	#[test]
+	fn generic_input_any() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_generic_input_any(|_| Box::new(8usize));
+
+			assert_eq!(MockTest::generic_input(1i8, 2u8), 8);
+			assert_eq!(MockTest::generic_input(3i16, 4u16), 8);
+		});
+	}
+
+	#[test]
+	fn generic_output_any() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_generic_output_any(|type_name| match type_name {
+				"i8" => Box::new(8i8),
+				_ => Box::new(16i16),
+			});
+
+			assert_eq!(MockTest::generic_output::<i8>(), 8);
+			assert_eq!(MockTest::generic_output::<i16>(), 16);
+		});
+	}
+
+	#[test]
+	fn generic_output_typed_over_any() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_generic_output_any(|_| Box::new(16i16));
+			MockTest::mock_generic_output(|| 8i8);
+
+			assert_eq!(MockTest::generic_output::<i8>(), 8);
+			assert_eq!(MockTest::generic_output::<i16>(), 16);
+		});
+	}
+
+	#[test]
+	#[should_panic(expected = "Mock returned a value of other type, expected i32")]
+	fn generic_output_any_wrong_type() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_generic_output_any(|_| Box::new(16i16));
+
+			MockTest::generic_output::<i32>();
+		});
+	}
+
 	#[test]
 	fn reference() {
 		System::externalities().execute_with(|| {
@@ -290,6 +474,52 @@ mod test {
 		});
 	}
 
+	#[test]
+	fn mutable_reference() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_mutable_reference(|buf, value| buf.push(value));
+
+			let mut buf = vec![1];
+			MockTest::mutable_reference(&mut buf, 2);
+			assert_eq!(buf, vec![1, 2]);
+		});
+	}
+
+	#[test]
+	fn references_with_different_lifetimes() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_references(|buf, value| {
+				buf.push(*value);
+				buf.last().unwrap()
+			});
+
+			let mut buf = vec![1];
+			assert_eq!(MockTest::references(&mut buf, &2), &2);
+			assert_eq!(buf, vec![1, 2]);
+		});
+	}
+
+	#[test]
+	fn iterator() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_iter(|| mock_builder::iter::boxed([1, 2, 3]));
+
+			assert_eq!(MockTest::iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+		});
+	}
+
+	#[test]
+	fn iterator_from_reference() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_iter_reference(|values| mock_builder::iter::boxed(values.iter().rev()));
+
+			assert_eq!(
+				MockTest::iter_reference(&[1, 2, 3]).collect::<Vec<_>>(),
+				vec![&3, &2, &1]
+			);
+		});
+	}
+
 	#[test]
 	fn get_last_set() {
 		System::externalities().execute_with(|| {
@@ -329,4 +559,253 @@ mod test {
 			assert_eq!(MockTest::generic(), 23);
 		});
 	}
+
+	#[test]
+	fn dispatch() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_dispatch_with(0, |origin| {
+				frame_system::ensure_root(origin)?;
+				Ok(Pays::No.into())
+			});
+			MockTest::mock_dispatch_with(1, |_| Err(DispatchError::Other("err").into()));
+
+			let call_0 = RuntimeCall::MockTest(pallet_mock_test::Call::mock_dispatch { id: 0 });
+			let call_1 = RuntimeCall::MockTest(pallet_mock_test::Call::mock_dispatch { id: 1 });
+
+			assert_eq!(
+				call_0.clone().dispatch(RuntimeOrigin::root()),
+				Ok(PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: Pays::No,
+				})
+			);
+			assert_err!(
+				call_0
+					.dispatch(RuntimeOrigin::signed(1))
+					.map_err(|e| e.error),
+				DispatchError::BadOrigin
+			);
+			assert_err!(
+				call_1.dispatch(RuntimeOrigin::root()).map_err(|e| e.error),
+				DispatchError::Other("err")
+			);
+		});
+	}
+
+	#[test]
+	#[should_panic]
+	fn dispatch_not_configured() {
+		System::externalities().execute_with(|| {
+			MockTest::mock_dispatch_with(0, |_| Ok(().into()));
+
+			let call = RuntimeCall::MockTest(pallet_mock_test::Call::mock_dispatch { id: 1 });
+
+			let _ = call.dispatch(RuntimeOrigin::root());
+		});
+	}
+
+	#[mock_builder::test(runtime = Runtime)]
+	fn test_attribute() {
+		MockTest::mock_qux(|p1| &p1 == "hello");
+
+		assert_eq!(MockTest::qux("hello".into()), true);
+	}
+
+	#[mock_builder::test(runtime = Runtime, block = 10)]
+	fn test_attribute_with_block() {
+		assert_eq!(System::block_number(), 10);
+	}
+
+	#[mock_builder::test(runtime = Runtime, genesis = RuntimeGenesisConfig::default())]
+	fn test_attribute_with_genesis() -> Result<(), String> {
+		assert_eq!(System::block_number(), 1);
+
+		MockTest::mock_bar(|_, _| Ok(()));
+
+		MockTest::bar(42, true)
+	}
+
+	#[mock_builder::test(runtime = Runtime)]
+	fn test_attribute_with_unused_mock() {
+		MockTest::mock_qux(|_| true);
+
+		assert_eq!(mock_builder::unused_mocks().len(), 1);
+	}
+
+	#[test]
+	fn test_attribute_clears_mocks() {
+		test_attribute_with_unused_mock();
+
+		assert!(mock_builder::unused_mocks().is_empty());
+	}
+
+	#[mock_builder::test(runtime = Runtime, strict = true)]
+	#[should_panic(expected = "Mock already registered")]
+	fn strict_overwritten_mock() {
+		MockTest::mock_qux(|_| true);
+		MockTest::mock_qux(|_| false);
+	}
+
+	#[mock_builder::test(runtime = Runtime, strict = true)]
+	fn strict_replaced_mock() {
+		MockTest::mock_qux(|_| true);
+		mock_builder::replace(|| MockTest::mock_qux(|_| false));
+
+		assert_eq!(MockTest::qux("hello".into()), false);
+	}
+
+	#[mock_builder::test(runtime = Runtime, strict = true)]
+	fn strict_lax_mock() {
+		mock_builder::strict::lax(|| {
+			MockTest::mock_qux(|_| true);
+			MockTest::mock_get(|| 23);
+		});
+		MockTest::mock_qux(|_| false);
+
+		assert_eq!(MockTest::qux("hello".into()), false);
+	}
+
+	#[mock_builder::test(runtime = Runtime, strict = true)]
+	#[should_panic(expected = "never executed in strict mode")]
+	fn strict_unused_mock() {
+		MockTest::mock_qux(|_| true);
+	}
+
+	#[mock_builder::test(runtime = Runtime, strict = true)]
+	fn strict_get_last_set() {
+		MockTest::mock_set(|v| mock_builder::replace(|| MockTest::mock_get(move || v)));
+
+		MockTest::set(23);
+		assert_eq!(MockTest::get(), 23);
+
+		MockTest::set(42);
+		assert_eq!(MockTest::get(), 42);
+	}
+
+	#[mock_builder::test(runtime = Runtime)]
+	#[should_panic(expected = "never executed in strict mode")]
+	fn strict_for_pallet() {
+		mock_builder::strict::enable_for::<MockTest>();
+
+		MockTest::mock_qux(|_| true);
+	}
+
+	#[mock_builder::test(runtime = Runtime)]
+	fn strict_for_other_pallet() {
+		mock_builder::strict::enable_for::<System>();
+
+		MockTest::mock_qux(|_| true);
+		MockTest::mock_qux(|_| false);
+	}
+
+	#[mock_builder::test(runtime = Runtime, golden = "tests/golden/correct_flow.txt")]
+	fn golden_correct_flow() {
+		MockTest::mock_foo(|p1, _| assert_eq!("hello", &p1));
+		MockTest::mock_qux(|p1| &p1 == "hello");
+		MockTest::mock_bar(|_, p2| match p2 {
+			true => Ok(()),
+			false => Err("err".into()),
+		});
+
+		assert_ok!(MyPallet::my_call("hello".into(), 42));
+	}
+
+	#[mock_builder::test(runtime = Runtime)]
+	fn default_outputs() {
+		MockTest::use_defaults();
+
+		assert_ok!(MyPallet::my_call("hello", 42));
+	}
+
+	#[mock_builder::test(runtime = Runtime)]
+	fn default_outputs_with_registered_mock() {
+		MockTest::use_defaults();
+		MockTest::mock_bar(|_, p2| match p2 {
+			true => Ok(()),
+			false => Err("err".into()),
+		});
+
+		assert_err!(MyPallet::my_call("hello", 42), "err");
+	}
+
+	#[mock_builder::test(runtime = Runtime)]
+	fn instances() {
+		MockInstance1::mock_get(|| 1);
+		MockInstance2::mock_get(|| 2);
+
+		assert_eq!(MockInstance1::get(), 1);
+		assert_eq!(MockInstance2::get(), 2);
+	}
+
+	#[mock_builder::test(runtime = Runtime)]
+	#[should_panic(expected = "Mock was not found")]
+	fn instance_mock_not_leaked() {
+		MockInstance1::mock_get(|| 1);
+
+		MockInstance2::get();
+	}
+
+	#[mock_builder::test(runtime = Runtime)]
+	fn instance_mocks_listed_and_cleared() {
+		MockInstance1::mock_set(|_| ());
+		MockInstance2::mock_set(|_| ());
+		MockInstance2::mock_get(|| 2);
+		MockInstance2::get();
+
+		assert_eq!(
+			mock_builder::registered_mocks_for::<MockInstance1>().len(),
+			1
+		);
+		assert_eq!(
+			mock_builder::registered_mocks_for::<MockInstance2>().len(),
+			2
+		);
+		assert_eq!(mock_builder::unused_mocks_for::<MockInstance2>().len(), 1);
+
+		mock_builder::clear_for::<MockInstance1>();
+
+		assert!(mock_builder::registered_mocks_for::<MockInstance1>().is_empty());
+		assert_eq!(
+			mock_builder::registered_mocks_for::<MockInstance2>().len(),
+			2
+		);
+		MockInstance2::set(3);
+	}
+
+	#[mock_builder::test(runtime = Runtime)]
+	fn instance_defaults() {
+		MockInstance1::use_defaults();
+		MockInstance2::mock_get(|| 2);
+
+		assert_eq!(MockInstance1::get(), 0);
+		assert_eq!(MockInstance2::get(), 2);
+	}
+
+	#[test]
+	fn snapshot_restored_in_other_externalities() {
+		let baseline = System::externalities().execute_with(|| {
+			MockTest::mock_qux(|p1| &p1 == "hello");
+			MockTest::mock_bar(|_, p2| match p2 {
+				true => Ok(()),
+				false => Err("err".into()),
+			});
+			mock_builder::snapshot()
+		});
+
+		for (name, expected) in [("hello", Ok(())), ("bye", Err(String::from("err")))] {
+			System::externalities().execute_with(|| {
+				mock_builder::restore(&baseline);
+				MockTest::mock_foo(move |p1, _| assert_eq!(name, &p1));
+
+				assert_eq!(MyPallet::my_call(name, 42), expected);
+			});
+		}
+	}
+
+	#[test]
+	fn benchmark_defaults() {
+		mock_builder::benchmark_externalities!(Runtime).execute_with(|| {
+			assert!(MockTest::qux("hello".into()));
+		});
+	}
 }
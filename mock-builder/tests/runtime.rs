@@ -0,0 +1,94 @@
+use frame_support::traits::{ConstU32, ConstU64, Get};
+
+pub trait Storage {
+	fn set(value: u32);
+	fn get() -> u32;
+}
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet_under_test {
+	use frame_support::pallet_prelude::*;
+
+	use super::Storage;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Storage: Storage;
+		type Max: Get<u32>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	impl<T: Config> Pallet<T> {
+		pub fn increment() -> u32 {
+			let value = T::Storage::get().saturating_add(1).min(T::Max::get());
+			T::Storage::set(value);
+			value
+		}
+	}
+}
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet_mock_storage {
+	use frame_support::pallet_prelude::*;
+	use mock_builder::{execute_call, register_call};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::storage]
+	type CallIds<T: Config> = StorageMap<_, _, String, mock_builder::CallId>;
+
+	impl<T: Config> Pallet<T> {
+		pub fn mock_set(f: impl Fn(u32) + 'static) {
+			register_call!(f);
+		}
+
+		pub fn mock_get(f: impl Fn() -> u32 + 'static) {
+			register_call!(move |()| f());
+		}
+	}
+
+	impl<T: Config> super::Storage for Pallet<T> {
+		fn set(a: u32) {
+			execute_call!(a)
+		}
+
+		fn get() -> u32 {
+			execute_call!(())
+		}
+	}
+}
+
+mock_builder::mock_runtime! {
+	pub struct Runtime;
+
+	System: frame_system {
+		type BlockHashCount = ConstU64<250>;
+	}
+
+	MyPallet: pallet_under_test {
+		type Storage => pallet_mock_storage;
+		type Max = ConstU32<10>;
+	}
+}
+
+#[mock_builder::test(runtime = Runtime, strict = true)]
+fn mocked_from_runtime() {
+	MockStorage::mock_get(|| 9);
+	MockStorage::mock_set(|value| assert_eq!(value, 10));
+
+	assert_eq!(MyPallet::increment(), 10);
+}
+
+#[test]
+fn system_config() {
+	assert_eq!(
+		<Runtime as frame_system::Config>::BlockHashCount::get(),
+		250
+	);
+}
@@ -40,9 +40,6 @@ mod benchmarks {
 		let caller: T::AccountId = account("acc_0", 0, 0);
 		let call: <T as Config>::RuntimeCall = frame_system::Call::remark { remark: vec![] }.into();
 
-		#[cfg(test)]
-		crate::mock::configure_mocks();
-
 		#[extrinsic_call]
 		remark(
 			RawOrigin::Signed(caller),
@@ -57,7 +54,7 @@ mod benchmarks {
 
 	impl_benchmark_test_suite!(
 		Pallet,
-		crate::mock::System::externalities(),
+		mock_builder::benchmark_externalities!(crate::mock::Runtime),
 		crate::mock::Runtime
 	);
 }
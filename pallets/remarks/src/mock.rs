@@ -9,11 +9,10 @@
 // but WITHOUT ANY WARRANTY; without even the implied warranty of
 // MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
 // GNU General Public License for more details.
-use frame_support::{
-	construct_runtime, derive_impl, pallet_prelude::ConstU32, parameter_types,
-	traits::InstanceFilter, BoundedVec,
-};
-use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{construct_runtime, derive_impl, pallet_prelude::ConstU32, BoundedVec};
+use mock_builder::MockGet;
+use mock_builder_pallets::{pallet_mock_filter, MockInstanceFilter};
+use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use sp_runtime::traits::BlakeTwo256;
 
@@ -28,6 +27,7 @@ construct_runtime!(
 		Remarks: pallet_remarks,
 		Utility: pallet_utility,
 		Proxy: pallet_proxy,
+		MockProxyFilter: pallet_mock_filter,
 	}
 );
 
@@ -44,9 +44,8 @@ impl pallet_balances::Config for Runtime {
 
 pub type Balance = u64;
 
-parameter_types! {
-	pub const MaxRemarksPerCall: u32 = 3;
-}
+/// Resolves to 3 unless a test mocks it.
+pub type MaxRemarksPerCall = MockGet<ConstU32<3>, u32>;
 
 #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo)]
 pub enum TestRemark {
@@ -64,9 +63,10 @@ impl Default for TestRemark {
 
 #[allow(unused_imports)]
 #[frame_support::pallet(dev_mode)]
-mod pallet_mock_test {
+pub mod pallet_mock_test {
 	use frame_support::pallet_prelude::*;
-	use mock_builder::{execute_call, register_call};
+	use frame_system::pallet_prelude::*;
+	use mock_builder::{execute_call, execute_dispatch, register_call, register_dispatch};
 
 	use crate::{RemarkArgs, RemarkDispatchHandler};
 
@@ -80,6 +80,10 @@ mod pallet_mock_test {
 	pub(super) type CallIds<T: Config> = StorageMap<_, _, String, mock_builder::CallId>;
 
 	impl<T: Config> Pallet<T> {
+		pub fn use_defaults() {
+			mock_builder::defaults::enable_for::<Self>();
+		}
+
 		pub fn mock_pre_dispatch_check(f: impl Fn(RemarkArgs<T>) -> DispatchResult + 'static) {
 			register_call!(move |t| f(t));
 		}
@@ -87,15 +91,30 @@ mod pallet_mock_test {
 		pub fn mock_post_dispatch_check(f: impl Fn(RemarkArgs<T>) -> DispatchResult + 'static) {
 			register_call!(move |t| f(t));
 		}
+
+		pub fn mock_dispatch_with(
+			id: u32,
+			f: impl Fn(OriginFor<T>) -> DispatchResultWithPostInfo + 'static,
+		) {
+			register_dispatch!(id, f);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		pub fn mock_dispatch(origin: OriginFor<T>, id: u32) -> DispatchResultWithPostInfo {
+			execute_dispatch!(id, origin)
+		}
 	}
 
 	impl<T: Config> RemarkDispatchHandler<RemarkArgs<T>> for Pallet<T> {
 		fn pre_dispatch_check(t: RemarkArgs<T>) -> DispatchResult {
-			execute_call!(t)
+			execute_call!(t, default = Ok(()))
 		}
 
 		fn post_dispatch_check(t: RemarkArgs<T>) -> DispatchResult {
-			execute_call!(t)
+			execute_call!(t, default = Ok(()))
 		}
 	}
 }
@@ -118,28 +137,7 @@ impl pallet_utility::Config for Runtime {
 	type WeightInfo = ();
 }
 
-#[derive(
-	Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, MaxEncodedLen, TypeInfo,
-)]
-pub enum ProxyType {
-	Any,
-	None,
-}
-
-impl Default for ProxyType {
-	fn default() -> Self {
-		Self::Any
-	}
-}
-
-impl InstanceFilter<RuntimeCall> for ProxyType {
-	fn filter(&self, _c: &RuntimeCall) -> bool {
-		match self {
-			ProxyType::Any => true,
-			ProxyType::None => false,
-		}
-	}
-}
+impl pallet_mock_filter::Config for Runtime {}
 
 impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositBase = ();
@@ -150,14 +148,15 @@ impl pallet_proxy::Config for Runtime {
 	type MaxProxies = ();
 	type ProxyDepositBase = ();
 	type ProxyDepositFactor = ();
-	type ProxyType = ProxyType;
+	type ProxyType = MockInstanceFilter<Runtime>;
 	type RuntimeCall = RuntimeCall;
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
 }
 
-#[allow(unused)]
-pub fn configure_mocks() {
-	RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
-	RemarkDispatchHandlerMock::mock_post_dispatch_check(move |_t| Ok(()));
+impl mock_builder::BenchmarkDefaults for Runtime {
+	fn register_defaults() {
+		RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
+		RemarkDispatchHandlerMock::mock_post_dispatch_check(move |_t| Ok(()));
+	}
 }
@@ -10,10 +10,11 @@
 // MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
 // GNU General Public License for more details.
 use frame_support::{assert_noop, assert_ok, pallet_prelude::ConstU32, BoundedVec};
-use frame_system::Call as SystemCall;
+use frame_system::{ensure_signed, Call as SystemCall};
 use pallet_balances::Call as BalancesCall;
 use pallet_proxy::Call as ProxyCall;
 use pallet_utility::Call as UtilityCall;
+use parity_scale_codec::{Decode, Encode};
 use sp_runtime::DispatchError::BadOrigin;
 
 use crate::{mock::*, *};
@@ -33,228 +34,416 @@ mod remark {
 		.expect("can build remarks")
 	}
 
-	#[test]
+	#[mock_builder::test(runtime = Runtime)]
 	fn success() {
-		System::externalities().execute_with(|| {
-			let origin = RuntimeOrigin::signed(1);
-			let remarks = get_test_remarks();
+		let origin = RuntimeOrigin::signed(1);
+		let remarks = get_test_remarks();
 
-			let call = RuntimeCall::System(SystemCall::remark {
+		let call = RuntimeCall::System(SystemCall::remark {
+			remark: vec![3, 4, 5],
+		});
+
+		let expected_pre_origin = origin.clone();
+		let expected_pre_remarks = remarks.clone();
+		let expected_pre_call = call.clone();
+
+		RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |t| {
+			assert_eq!(t.0.into_signer(), expected_pre_origin.clone().into_signer());
+			assert_eq!(t.1, expected_pre_remarks);
+			assert_eq!(t.2, Box::new(expected_pre_call.clone()));
+
+			Ok(())
+		});
+
+		let expected_post_origin = origin.clone();
+		let expected_post_remarks = remarks.clone();
+		let expected_post_call = call.clone();
+
+		RemarkDispatchHandlerMock::mock_post_dispatch_check(move |t| {
+			assert_eq!(
+				t.0.into_signer(),
+				expected_post_origin.clone().into_signer()
+			);
+			assert_eq!(t.1, expected_post_remarks);
+			assert_eq!(t.2, Box::new(expected_post_call.clone()));
+
+			Ok(())
+		});
+
+		assert_ok!(Remarks::remark(
+			origin.clone(),
+			remarks.clone(),
+			call.clone().into()
+		));
+
+		let expected_event: RuntimeEvent = Event::<Runtime>::Remark { remarks, call }.into();
+
+		System::events()
+			.iter()
+			.find(|e| e.event == expected_event)
+			.expect("remark event is present");
+	}
+
+	#[mock_builder::test(runtime = Runtime)]
+	fn mocked_max_remarks() {
+		let remarks = get_test_remarks();
+
+		let call = RuntimeCall::Remarks(Call::remark {
+			remarks: remarks.clone(),
+			call: Box::new(RuntimeCall::System(SystemCall::remark {
 				remark: vec![3, 4, 5],
-			});
+			})),
+		});
 
-			let expected_pre_origin = origin.clone();
-			let expected_pre_remarks = remarks.clone();
-			let expected_pre_call = call.clone();
+		MaxRemarksPerCall::set(2);
 
-			RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |t| {
-				assert_eq!(t.0.into_signer(), expected_pre_origin.clone().into_signer());
-				assert_eq!(t.1, expected_pre_remarks);
-				assert_eq!(t.2, Box::new(expected_pre_call.clone()));
+		assert!(BoundedVec::<TestRemark, MaxRemarksPerCall>::try_from(remarks.to_vec()).is_err());
+		assert!(RuntimeCall::decode(&mut &call.encode()[..]).is_err());
 
-				Ok(())
-			});
+		let remarks =
+			BoundedVec::try_from(remarks[..2].to_vec()).expect("remarks within the bound");
+		let call = RuntimeCall::System(SystemCall::remark {
+			remark: vec![3, 4, 5],
+		});
 
-			let expected_post_origin = origin.clone();
-			let expected_post_remarks = remarks.clone();
-			let expected_post_call = call.clone();
+		RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |t| {
+			assert_eq!(t.1.len(), 2);
+			Ok(())
+		});
+		RemarkDispatchHandlerMock::mock_post_dispatch_check(move |_t| Ok(()));
 
-			RemarkDispatchHandlerMock::mock_post_dispatch_check(move |t| {
-				assert_eq!(
-					t.0.into_signer(),
-					expected_post_origin.clone().into_signer()
-				);
-				assert_eq!(t.1, expected_post_remarks);
-				assert_eq!(t.2, Box::new(expected_post_call.clone()));
+		assert_ok!(Remarks::remark(
+			RuntimeOrigin::signed(1),
+			remarks,
+			call.into()
+		));
+	}
 
-				Ok(())
-			});
+	#[mock_builder::test(runtime = Runtime)]
+	fn no_remarks() {
+		let call = RuntimeCall::System(SystemCall::remark {
+			remark: vec![3, 4, 5],
+		});
 
-			assert_ok!(Remarks::remark(
-				origin.clone(),
-				remarks.clone(),
+		assert_noop!(
+			Remarks::remark(
+				RuntimeOrigin::signed(1),
+				Default::default(),
 				call.clone().into()
-			));
+			),
+			Error::<Runtime>::NoRemarks,
+		);
+	}
 
-			let expected_event: RuntimeEvent = Event::<Runtime>::Remark { remarks, call }.into();
+	#[mock_builder::test(runtime = Runtime)]
+	fn pre_dispatch_failure() {
+		let remarks = get_test_remarks();
 
-			System::events()
-				.iter()
-				.find(|e| e.event == expected_event)
-				.expect("remark event is present");
+		let call = RuntimeCall::System(SystemCall::remark {
+			remark: vec![3, 4, 5],
 		});
+
+		let expected_error = DispatchError::Other("pre-dispatch error");
+
+		RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Err(expected_error));
+
+		assert_noop!(
+			Remarks::remark(
+				RuntimeOrigin::signed(1),
+				remarks.clone(),
+				call.clone().into()
+			),
+			expected_error
+		);
 	}
 
-	#[test]
-	fn no_remarks() {
-		System::externalities().execute_with(|| {
-			let call = RuntimeCall::System(SystemCall::remark {
-				remark: vec![3, 4, 5],
-			});
+	#[mock_builder::test(runtime = Runtime)]
+	fn post_dispatch_failure() {
+		let remarks = get_test_remarks();
 
-			assert_noop!(
-				Remarks::remark(
-					RuntimeOrigin::signed(1),
-					Default::default(),
-					call.clone().into()
-				),
-				Error::<Runtime>::NoRemarks,
-			);
+		let call = RuntimeCall::System(SystemCall::remark {
+			remark: vec![3, 4, 5],
 		});
+
+		RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
+
+		let expected_error = DispatchError::Other("post-dispatch error");
+
+		RemarkDispatchHandlerMock::mock_post_dispatch_check(move |_t| Err(expected_error));
+
+		assert_noop!(
+			Remarks::remark(
+				RuntimeOrigin::signed(1),
+				remarks.clone(),
+				call.clone().into()
+			),
+			expected_error
+		);
 	}
 
-	#[test]
-	fn pre_dispatch_failure() {
-		System::externalities().execute_with(|| {
-			let remarks = get_test_remarks();
+	#[mock_builder::test(runtime = Runtime)]
+	fn nested_remark_call_failure() {
+		let remarks = get_test_remarks();
 
-			let call = RuntimeCall::System(SystemCall::remark {
+		let call = RuntimeCall::Remarks(Call::remark {
+			remarks: Default::default(),
+			call: Box::new(RuntimeCall::System(SystemCall::remark {
 				remark: vec![3, 4, 5],
-			});
+			})),
+		});
 
-			let expected_error = DispatchError::Other("pre-dispatch error");
+		RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
 
-			RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Err(expected_error));
+		assert_noop!(
+			Remarks::remark(
+				RuntimeOrigin::signed(1),
+				remarks.clone(),
+				call.clone().into()
+			),
+			frame_system::Error::<Runtime>::CallFiltered
+		);
+	}
 
-			assert_noop!(
-				Remarks::remark(
-					RuntimeOrigin::signed(1),
-					remarks.clone(),
-					call.clone().into()
-				),
-				expected_error
-			);
+	#[mock_builder::test(runtime = Runtime)]
+	fn remark_in_batch_call_success() {
+		let remarks = get_test_remarks();
+
+		let batch_call = RuntimeCall::Utility(UtilityCall::batch {
+			calls: vec![
+				RuntimeCall::Remarks(Call::remark {
+					remarks: Default::default(),
+					call: Box::new(RuntimeCall::System(SystemCall::remark {
+						remark: vec![1, 2, 3],
+					})),
+				}),
+				RuntimeCall::Remarks(Call::remark {
+					remarks: Default::default(),
+					call: Box::new(RuntimeCall::System(SystemCall::remark {
+						remark: vec![4, 5, 6],
+					})),
+				}),
+			],
 		});
+
+		RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
+		RemarkDispatchHandlerMock::mock_post_dispatch_check(move |_t| Ok(()));
+
+		assert_ok!(Remarks::remark(
+			RuntimeOrigin::signed(1),
+			remarks.clone(),
+			batch_call.clone().into()
+		));
 	}
 
-	#[test]
-	fn post_dispatch_failure() {
-		System::externalities().execute_with(|| {
-			let remarks = get_test_remarks();
+	#[mock_builder::test(runtime = Runtime)]
+	fn inner_call_failure() {
+		let remarks = get_test_remarks();
 
-			let call = RuntimeCall::System(SystemCall::remark {
-				remark: vec![3, 4, 5],
-			});
+		let call = RuntimeCall::System(SystemCall::set_heap_pages { pages: 8 });
 
-			RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
+		RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
 
-			let expected_error = DispatchError::Other("post-dispatch error");
+		assert_noop!(
+			Remarks::remark(
+				RuntimeOrigin::signed(1),
+				remarks.clone(),
+				call.clone().into()
+			),
+			BadOrigin
+		);
+	}
 
-			RemarkDispatchHandlerMock::mock_post_dispatch_check(move |_t| Err(expected_error));
+	#[mock_builder::test(runtime = Runtime)]
+	fn inner_mock_call_success() {
+		let remarks = get_test_remarks();
 
-			assert_noop!(
-				Remarks::remark(
-					RuntimeOrigin::signed(1),
-					remarks.clone(),
-					call.clone().into()
-				),
-				expected_error
-			);
+		let call =
+			RuntimeCall::RemarkDispatchHandlerMock(pallet_mock_test::Call::mock_dispatch { id: 0 });
+
+		RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
+		RemarkDispatchHandlerMock::mock_dispatch_with(0, |origin| {
+			assert_eq!(ensure_signed(origin)?, 1);
+			Ok(().into())
 		});
+		RemarkDispatchHandlerMock::mock_post_dispatch_check(move |_t| Ok(()));
+
+		assert_ok!(Remarks::remark(
+			RuntimeOrigin::signed(1),
+			remarks.clone(),
+			call.clone().into()
+		));
 	}
 
-	#[test]
-	fn nested_remark_call_failure() {
-		System::externalities().execute_with(|| {
-			let remarks = get_test_remarks();
+	#[mock_builder::test(runtime = Runtime)]
+	fn inner_mock_call_failure() {
+		let remarks = get_test_remarks();
 
-			let call = RuntimeCall::Remarks(Call::remark {
-				remarks: Default::default(),
-				call: Box::new(RuntimeCall::System(SystemCall::remark {
-					remark: vec![3, 4, 5],
-				})),
-			});
+		let call =
+			RuntimeCall::RemarkDispatchHandlerMock(pallet_mock_test::Call::mock_dispatch { id: 0 });
 
-			RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
+		let expected_error = DispatchError::Other("inner call error");
 
-			assert_noop!(
-				Remarks::remark(
-					RuntimeOrigin::signed(1),
-					remarks.clone(),
-					call.clone().into()
-				),
-				frame_system::Error::<Runtime>::CallFiltered
-			);
+		RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
+		RemarkDispatchHandlerMock::mock_dispatch_with(0, move |_| Err(expected_error.into()));
+
+		assert_noop!(
+			Remarks::remark(
+				RuntimeOrigin::signed(1),
+				remarks.clone(),
+				call.clone().into()
+			),
+			expected_error
+		);
+	}
+
+	#[mock_builder::test(runtime = Runtime)]
+	fn success_with_default_checks() {
+		let remarks = get_test_remarks();
+
+		let call = RuntimeCall::System(SystemCall::remark {
+			remark: vec![3, 4, 5],
 		});
+
+		RemarkDispatchHandlerMock::use_defaults();
+
+		assert_ok!(Remarks::remark(
+			RuntimeOrigin::signed(1),
+			remarks.clone(),
+			call.clone().into()
+		));
 	}
 
-	#[test]
-	fn remark_in_batch_call_success() {
-		System::externalities().execute_with(|| {
-			let remarks = get_test_remarks();
-
-			let batch_call = RuntimeCall::Utility(UtilityCall::batch {
-				calls: vec![
-					RuntimeCall::Remarks(Call::remark {
-						remarks: Default::default(),
-						call: Box::new(RuntimeCall::System(SystemCall::remark {
-							remark: vec![1, 2, 3],
-						})),
-					}),
-					RuntimeCall::Remarks(Call::remark {
-						remarks: Default::default(),
-						call: Box::new(RuntimeCall::System(SystemCall::remark {
-							remark: vec![4, 5, 6],
-						})),
-					}),
-				],
-			});
-
-			RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
-			RemarkDispatchHandlerMock::mock_post_dispatch_check(move |_t| Ok(()));
-
-			assert_ok!(Remarks::remark(
+	#[mock_builder::test(runtime = Runtime)]
+	fn post_dispatch_failure_with_default_checks() {
+		let remarks = get_test_remarks();
+
+		let call = RuntimeCall::System(SystemCall::remark {
+			remark: vec![3, 4, 5],
+		});
+
+		RemarkDispatchHandlerMock::use_defaults();
+
+		let expected_error = DispatchError::Other("post-dispatch error");
+
+		RemarkDispatchHandlerMock::mock_post_dispatch_check(move |_t| Err(expected_error));
+
+		assert_noop!(
+			Remarks::remark(
 				RuntimeOrigin::signed(1),
 				remarks.clone(),
-				batch_call.clone().into()
-			));
+				call.clone().into()
+			),
+			expected_error
+		);
+	}
+
+	#[mock_builder::test(runtime = Runtime)]
+	fn inner_proxy_call_failure() {
+		let remarks = get_test_remarks();
+
+		let proxy_origin = RuntimeOrigin::signed(1);
+		let real_origin = RuntimeOrigin::signed(2);
+		let transfer_dest = RuntimeOrigin::signed(3);
+
+		let call = RuntimeCall::Proxy(ProxyCall::proxy {
+			real: real_origin.into_signer().unwrap(),
+			force_proxy_type: None,
+			call: Box::new(RuntimeCall::Balances(BalancesCall::transfer_allow_death {
+				dest: transfer_dest.into_signer().unwrap(),
+				value: 100,
+			})),
 		});
+
+		RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
+
+		assert_noop!(
+			Remarks::remark(proxy_origin, remarks.clone(), call.clone().into()),
+			pallet_proxy::Error::<Runtime>::NotProxy,
+		);
 	}
 
-	#[test]
-	fn inner_call_failure() {
-		System::externalities().execute_with(|| {
-			let remarks = get_test_remarks();
+	#[mock_builder::test(runtime = Runtime, strict = true)]
+	#[should_panic(expected = "Mocks registered but never executed in strict mode")]
+	fn unused_check_in_strict_mode() {
+		let call = RuntimeCall::System(SystemCall::remark {
+			remark: vec![3, 4, 5],
+		});
+
+		RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
+		RemarkDispatchHandlerMock::mock_post_dispatch_check(move |_t| Ok(()));
+
+		// The remark call is rejected before any check is executed.
+		assert_noop!(
+			Remarks::remark(
+				RuntimeOrigin::signed(1),
+				Default::default(),
+				call.clone().into()
+			),
+			Error::<Runtime>::NoRemarks,
+		);
+	}
+}
+
+mod arbitrary_dispatch_checks {
+	use mock_builder::prop::MockOutputs;
+	use proptest::prelude::*;
 
-			let call = RuntimeCall::System(SystemCall::set_heap_pages { pages: 8 });
+	use super::*;
+
+	const REJECTED: DispatchError = DispatchError::Other("rejected");
+
+	fn check(accepted: bool) -> DispatchResult {
+		match accepted {
+			true => Ok(()),
+			false => Err(REJECTED),
+		}
+	}
 
-			RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
+	fn remark_calls(
+		pre_checks: MockOutputs<bool>,
+		post_checks: MockOutputs<bool>,
+		calls: usize,
+	) -> Result<(), TestCaseError> {
+		let remarks = BoundedVec::try_from(vec![TestRemark::SomeId(1)]).unwrap();
+		let call = RuntimeCall::System(SystemCall::remark { remark: vec![] });
 
-			assert_noop!(
+		let expected_pre_checks = pre_checks.clone();
+		let expected_post_checks = post_checks.clone();
+
+		RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_| check(pre_checks.next()));
+		RemarkDispatchHandlerMock::mock_post_dispatch_check(move |_| check(post_checks.next()));
+
+		for _ in 0..calls {
+			let expected = match expected_pre_checks.next() {
+				true => check(expected_post_checks.next()),
+				false => Err(REJECTED),
+			};
+
+			prop_assert_eq!(
 				Remarks::remark(
 					RuntimeOrigin::signed(1),
 					remarks.clone(),
 					call.clone().into()
 				),
-				BadOrigin
+				expected
 			);
-		});
+		}
+
+		Ok(())
 	}
 
-	#[test]
-	fn inner_proxy_call_failure() {
-		System::externalities().execute_with(|| {
-			let remarks = get_test_remarks();
-
-			let proxy_origin = RuntimeOrigin::signed(1);
-			let real_origin = RuntimeOrigin::signed(2);
-			let transfer_dest = RuntimeOrigin::signed(3);
-
-			let call = RuntimeCall::Proxy(ProxyCall::proxy {
-				real: real_origin.into_signer().unwrap(),
-				force_proxy_type: None,
-				call: Box::new(RuntimeCall::Balances(BalancesCall::transfer_allow_death {
-					dest: transfer_dest.into_signer().unwrap(),
-					value: 100,
-				})),
-			});
-
-			RemarkDispatchHandlerMock::mock_pre_dispatch_check(move |_t| Ok(()));
-
-			assert_noop!(
-				Remarks::remark(proxy_origin, remarks.clone(), call.clone().into()),
-				pallet_proxy::Error::<Runtime>::NotProxy,
-			);
-		});
+	proptest! {
+		#[test]
+		fn remark(
+			pre_checks in any::<MockOutputs<bool>>(),
+			post_checks in any::<MockOutputs<bool>>(),
+			calls in 1..8usize,
+		) {
+			let result = System::externalities()
+				.execute_with(|| remark_calls(pre_checks, post_checks, calls));
+
+			mock_builder::clear();
+			result?;
+		}
 	}
 }